@@ -0,0 +1,16 @@
+export function defineCustomElement(name, observedAttributes, connected, disconnected, attributeChanged) {
+    customElements.define(name, class extends HTMLElement {
+        static get observedAttributes() {
+            return observedAttributes;
+        }
+        connectedCallback() {
+            connected(this);
+        }
+        disconnectedCallback() {
+            disconnected(this);
+        }
+        attributeChangedCallback(name, oldValue, newValue) {
+            attributeChanged(this, name, newValue);
+        }
+    });
+}
@@ -1,75 +1,353 @@
 use crate::{
+    effects::{self, Deferred, Effects},
     env::Env,
-    render::{RenderContext, VNode},
+    error::ErrorKind,
+    render::{DecodeErrors, RenderContext, VNode},
+    stylesheet::StyleSheet,
+    subscription::{Subscription, Subscriptions},
 };
 use futures::{
-    channel::mpsc, //
+    channel::{mpsc, oneshot},
     prelude::*,
     stream::{FusedStream, Stream},
     task::{self, Poll},
 };
-use siro::vdom::Nodes;
-use std::pin::Pin;
-use wasm_bindgen::JsCast as _;
+use siro::{vdom::Nodes, Cmd};
+use std::{cell::RefCell, hash::Hash, pin::Pin, rc::Rc};
+use wasm_bindgen::prelude::*;
 
 pub struct App<'env, TMsg: 'static> {
     env: &'env Env,
     mountpoint: web::Node,
+    host: web::EventTarget,
     vnodes: Vec<VNode>,
+    deferred: Vec<Deferred>,
     tx: mpsc::UnboundedSender<TMsg>,
     rx: mpsc::UnboundedReceiver<TMsg>,
+    decode_errors: Rc<DecodeErrors<TMsg>>,
+    frame: Option<Frame>,
+    drop_behavior: DropBehavior,
+}
+
+/// What happens to the rendered DOM nodes when an `App` is dropped.
+///
+/// The event listeners registered by the application are removed in
+/// either case.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum DropBehavior {
+    /// Leave the rendered nodes in the document.  This is the default.
+    #[default]
+    Keep,
+    /// Remove the rendered nodes from the mountpoint, as `App::unmount` does.
+    Unmount,
+}
+
+thread_local! {
+    // The nodes on which the applications are currently mounted.
+    static MOUNTPOINTS: RefCell<Vec<web::Node>> = const { RefCell::new(vec![]) };
+}
+
+/// The event that the application waits for, returned from `App::next_step`.
+#[derive(Debug)]
+pub enum Step<TMsg> {
+    /// A message has arrived.
+    Message(TMsg),
+    /// The animation frame requested by `App::request_render` has come,
+    /// and the view should be rendered.
+    Render,
 }
 
 impl<'env, TMsg: 'static> App<'env, TMsg> {
-    pub(crate) fn new(env: &'env Env, mountpoint: web::Node) -> Self {
+    pub(crate) fn new(env: &'env Env, mountpoint: web::Node) -> crate::Result<Self> {
+        // Several applications can be mounted in a document as long as their
+        // mountpoints are different, since they share nothing but the document.
+        MOUNTPOINTS.with(|mountpoints| {
+            let mut mountpoints = mountpoints.borrow_mut();
+            if mountpoints
+                .iter()
+                .any(|node| node.is_same_node(Some(&mountpoint)))
+            {
+                return Err(crate::Error::new(ErrorKind::AlreadyMounted));
+            }
+            mountpoints.push(mountpoint.clone());
+            Ok(())
+        })?;
+
         let (tx, rx) = mpsc::unbounded();
-        Self {
+        Ok(Self {
             env,
+            host: mountpoint.clone().into(),
             mountpoint,
             vnodes: vec![],
+            deferred: vec![],
             tx,
             rx,
-        }
+            decode_errors: Rc::new(DecodeErrors::new()),
+            frame: None,
+            drop_behavior: DropBehavior::default(),
+        })
+    }
+
+    /// Specify the target of the events dispatched by `Effects::dispatch_event`,
+    /// which is the mountpoint by default.
+    pub(crate) fn set_host(&mut self, host: web::EventTarget) {
+        self.host = host;
+    }
+
+    pub(crate) fn sender(&self) -> mpsc::UnboundedSender<TMsg> {
+        self.tx.clone()
     }
 
     pub fn send_message(&self, msg: TMsg) {
         let _ = self.tx.unbounded_send(msg);
     }
 
+    /// Execute the operations in the command and send their results as messages.
+    pub fn spawn(&self, cmd: Cmd<TMsg>) {
+        for task in cmd {
+            let tx = self.tx.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let msg = task.await;
+                let _ = tx.unbounded_send(msg);
+            });
+        }
+    }
+
     pub async fn next_message(&mut self) -> Option<TMsg> {
         self.next().await
     }
 
+    /// Take a message that has already arrived, without waiting for new ones.
+    pub fn try_next_message(&mut self) -> Option<TMsg> {
+        self.rx.try_recv().ok()
+    }
+
+    /// Schedule a render on the next animation frame.
+    ///
+    /// The requests are coalesced until the frame comes, so the view is
+    /// rendered at most once per frame however many messages are applied.
+    /// The frame is notified by `App::next_step` as `Step::Render`.
+    pub fn request_render(&mut self) -> crate::Result<()> {
+        if self.frame.is_none() {
+            self.frame = Some(Frame::request(&self.env.window)?);
+        }
+        Ok(())
+    }
+
+    /// Return whether a render has been requested and not performed yet.
+    pub fn is_render_requested(&self) -> bool {
+        self.frame.is_some()
+    }
+
+    /// Wait for the next message or the requested animation frame.
+    ///
+    /// `None` is returned if no more messages will arrive and no render
+    /// is requested.
+    pub async fn next_step(&mut self) -> Option<Step<TMsg>> {
+        future::poll_fn(|cx| self.poll_step(cx)).await
+    }
+
+    fn poll_step(&mut self, cx: &mut task::Context<'_>) -> Poll<Option<Step<TMsg>>> {
+        if let Some(ref mut frame) = self.frame {
+            if frame.rx.poll_unpin(cx).is_ready() {
+                self.frame = None;
+                return Poll::Ready(Some(Step::Render));
+            }
+        }
+
+        match futures::ready!(self.rx.poll_next_unpin(cx)) {
+            Some(msg) => Poll::Ready(Some(Step::Message(msg))),
+            None if self.frame.is_some() => Poll::Pending,
+            None => Poll::Ready(None),
+        }
+    }
+
+    /// Cancel the requested animation frame, and render the view immediately.
+    ///
+    /// This is useful when the DOM must be up to date synchronously,
+    /// e.g. in tests or before moving the focus to a newly created element.
+    pub fn flush<N>(&mut self, nodes: N) -> crate::Result<()>
+    where
+        N: Nodes<TMsg>,
+    {
+        self.frame.take();
+        self.render(nodes)
+    }
+
     pub fn render<N>(&mut self, nodes: N) -> crate::Result<()>
     where
         N: Nodes<TMsg>,
     {
+        let pending_hooks = RefCell::new(vec![]);
         RenderContext {
             document: &self.env.document,
             parent: &self.mountpoint,
             tx: &self.tx,
+            decode_errors: &self.decode_errors,
+            pending_hooks: &pending_hooks,
         }
         .diff_nodes(nodes, &mut self.vnodes)?;
+
+        // The mount and update hooks are called after the whole tree is attached.
+        for (hook, element) in pending_hooks.into_inner() {
+            hook.call(&element);
+        }
+
+        let env = self.scoped_env();
+        for deferred in self.deferred.drain(..) {
+            deferred.apply(&env)?;
+        }
+
         Ok(())
     }
 
-    pub fn focus(&self, id: &str) -> crate::Result<()> {
-        if let Some(element) = self.env.document.get_element_by_id(id) {
-            if let Ok(element) = element.dyn_into::<web::HtmlElement>() {
-                element.focus().map_err(crate::Error::caught_from_js)?;
-            }
+    /// Create an `Effects` that performs the managed effects on this application.
+    pub fn effects(&mut self) -> Effects<'_, TMsg> {
+        Effects {
+            env: self.env,
+            tx: &self.tx,
+            host: &self.host,
+            deferred: &mut self.deferred,
+        }
+    }
+
+    /// Create a `Subscriptions` that sends the messages to this application.
+    pub fn subscriptions<K>(&self) -> Subscriptions<'env, K, TMsg>
+    where
+        K: Eq + Hash,
+    {
+        Subscriptions::new(self.env, self.mountpoint.clone(), self.tx.clone())
+    }
+
+    /// Specify the function called when an event fails to be decoded.
+    ///
+    /// The returned message, if any, is sent to this application.
+    /// By default, the errors are logged to the console and discarded.
+    pub fn on_decode_error<F>(&mut self, f: F)
+    where
+        F: FnMut(crate::Error) -> Option<TMsg> + 'static,
+    {
+        self.decode_errors.set_handler(Box::new(f));
+    }
+
+    /// Log the decode errors of events to the console, and discard them.
+    pub fn log_decode_errors(&mut self) {
+        self.on_decode_error(|err| {
+            web::console::error_1(&err.into());
+            None
+        });
+    }
+
+    /// Silently discard the decode errors of events.
+    pub fn ignore_decode_errors(&mut self) {
+        self.on_decode_error(|_| None);
+    }
+
+    /// Return the number of events that have failed to be decoded so far.
+    pub fn decode_error_count(&self) -> usize {
+        self.decode_errors.count()
+    }
+
+    /// Apply the stylesheet to the root where this application is mounted.
+    ///
+    /// The stylesheet is adopted by the enclosing shadow root if the
+    /// application is mounted inside it, e.g. by `Env::mount_shadow` or in
+    /// a `CustomElement`, or by the document otherwise.
+    pub fn adopt_stylesheet(&self, stylesheet: &StyleSheet) -> crate::Result<()> {
+        let sheets = self.adopted_stylesheets();
+        if !sheets.includes(&stylesheet.sheet, 0) {
+            sheets.push(&stylesheet.sheet);
+            self.set_adopted_stylesheets(&sheets);
         }
         Ok(())
     }
 
-    pub fn blur(&self, id: &str) -> crate::Result<()> {
-        if let Some(element) = self.env.document.get_element_by_id(id) {
-            if let Ok(element) = element.dyn_into::<web::HtmlElement>() {
-                element.blur().map_err(crate::Error::caught_from_js)?;
-            }
+    /// Stop applying the stylesheet adopted by `adopt_stylesheet`.
+    pub fn remove_stylesheet(&self, stylesheet: &StyleSheet) -> crate::Result<()> {
+        let target: &JsValue = stylesheet.sheet.as_ref();
+        let sheets = self
+            .adopted_stylesheets()
+            .filter(&mut |sheet, _, _| sheet != *target);
+        self.set_adopted_stylesheets(&sheets);
+        Ok(())
+    }
+
+    fn adopted_stylesheets(&self) -> js_sys::Array {
+        let root = self.mountpoint.get_root_node();
+        // A copy of the array is returned, since the property must be
+        // reassigned to take effect.
+        let sheets = match root.dyn_ref::<web::ShadowRoot>() {
+            Some(shadow_root) => shadow_root.adopted_style_sheets(),
+            None => self.env.document.adopted_style_sheets(),
+        };
+        js_sys::Array::from(&sheets)
+    }
+
+    fn set_adopted_stylesheets(&self, sheets: &js_sys::Array) {
+        let root = self.mountpoint.get_root_node();
+        match root.dyn_ref::<web::ShadowRoot>() {
+            Some(shadow_root) => shadow_root.set_adopted_style_sheets(sheets),
+            None => self.env.document.set_adopted_style_sheets(sheets),
+        }
+    }
+
+    /// Specify what happens to the rendered nodes when this application is dropped.
+    pub fn set_drop_behavior(&mut self, behavior: DropBehavior) {
+        self.drop_behavior = behavior;
+    }
+
+    /// Remove the rendered nodes and their event listeners from the document,
+    /// and release the mountpoint.
+    ///
+    /// The other contents of the mountpoint are left untouched, and another
+    /// application can be mounted on it after this call.
+    pub fn unmount(mut self) -> crate::Result<()> {
+        self.remove_nodes()
+    }
+
+    fn remove_nodes(&mut self) -> crate::Result<()> {
+        self.frame.take();
+        self.deferred.clear();
+        for vnode in self.vnodes.drain(..) {
+            vnode.detach(&self.mountpoint)?;
         }
         Ok(())
     }
+
+    pub fn focus(&self, id: &str) -> crate::Result<()> {
+        effects::focus(&self.scoped_env(), id)
+    }
+
+    pub fn blur(&self, id: &str) -> crate::Result<()> {
+        effects::blur(&self.scoped_env(), id)
+    }
+
+    /// Start the subscription, resolving the element ids in the tree of the
+    /// mountpoint, which may be a shadow root.
+    pub fn subscribe<S>(&self, subscription: S) -> crate::Result<S::Stream>
+    where
+        S: Subscription,
+    {
+        subscription.subscribe(&self.scoped_env())
+    }
+
+    /// The environment in which the element ids are looked up.
+    fn scoped_env(&self) -> Env {
+        self.env.scoped(&self.mountpoint)
+    }
+}
+
+impl<TMsg: 'static> Drop for App<'_, TMsg> {
+    fn drop(&mut self) {
+        if self.drop_behavior == DropBehavior::Unmount {
+            let _ = self.remove_nodes();
+        }
+        MOUNTPOINTS.with(|mountpoints| {
+            mountpoints
+                .borrow_mut()
+                .retain(|node| !node.is_same_node(Some(&self.mountpoint)));
+        });
+    }
 }
 
 impl<TMsg: 'static> Stream for App<'_, TMsg> {
@@ -92,3 +370,35 @@ impl<TMsg: 'static> FusedStream for App<'_, TMsg> {
         self.rx.is_terminated()
     }
 }
+
+/// An animation frame requested for rendering, which is cancelled on drop.
+struct Frame {
+    window: web::Window,
+    id: i32,
+    rx: oneshot::Receiver<()>,
+    _cb: Closure<dyn FnMut()>,
+}
+
+impl Frame {
+    fn request(window: &web::Window) -> crate::Result<Self> {
+        let (tx, rx) = oneshot::channel();
+        let cb = Closure::once(move || {
+            let _ = tx.send(());
+        });
+        let id = window
+            .request_animation_frame(cb.as_ref().unchecked_ref())
+            .map_err(crate::Error::dom("requestAnimationFrame"))?;
+        Ok(Self {
+            window: window.clone(),
+            id,
+            rx,
+            _cb: cb,
+        })
+    }
+}
+
+impl Drop for Frame {
+    fn drop(&mut self) {
+        let _ = self.window.cancel_animation_frame(self.id);
+    }
+}
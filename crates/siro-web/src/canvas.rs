@@ -0,0 +1,260 @@
+use crate::error::ErrorKind;
+use gloo_events::EventListener;
+use siro::{
+    canvas::{Command, Drawing},
+    vdom::CowStr,
+};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    rc::{Rc, Weak},
+};
+use wasm_bindgen::prelude::*;
+
+/// The drawing replayed onto a `<canvas>` element.
+#[derive(Debug)]
+pub(crate) struct Canvas {
+    inner: Rc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    element: web::HtmlCanvasElement,
+    context: web::CanvasRenderingContext2d,
+    drawing: RefCell<Drawing>,
+    images: RefCell<HashMap<CowStr, Image>>,
+}
+
+#[derive(Debug)]
+struct Image {
+    element: web::HtmlImageElement,
+    _on_load: EventListener,
+}
+
+impl Canvas {
+    /// Start to manage the drawing on the element, and draw it.
+    pub(crate) fn new(node: &web::Element, drawing: Drawing) -> crate::Result<Self> {
+        let element: web::HtmlCanvasElement = node.clone().dyn_into().map_err(|_| {
+            let tag_name = node.tag_name().to_lowercase();
+            crate::Error::new(ErrorKind::NotCanvas(tag_name.into()))
+        })?;
+
+        let context = element
+            .get_context("2d")
+            .map_err(crate::Error::dom("HTMLCanvasElement.getContext"))?
+            .ok_or_else(|| crate::Error::new(ErrorKind::Unsupported("CanvasRenderingContext2D")))?
+            .unchecked_into();
+
+        let canvas = Self {
+            inner: Rc::new(Inner {
+                element,
+                context,
+                drawing: RefCell::new(drawing),
+                images: RefCell::new(HashMap::new()),
+            }),
+        };
+        canvas.inner.draw()?;
+
+        Ok(canvas)
+    }
+
+    /// Replace the drawing, and redraw it if it is changed.
+    ///
+    /// The `resized` flag forces redrawing, since resizing the canvas
+    /// also clears its content.
+    pub(crate) fn update(&self, drawing: Drawing, resized: bool) -> crate::Result<()> {
+        if !resized && *self.inner.drawing.borrow() == drawing {
+            return Ok(());
+        }
+        *self.inner.drawing.borrow_mut() = drawing;
+        self.inner.draw()
+    }
+
+    /// Clear the content of the canvas.
+    pub(crate) fn clear(&self) {
+        self.inner.reset();
+    }
+}
+
+impl Inner {
+    /// Clear the canvas and reset the state of the context.
+    fn reset(&self) {
+        self.element.set_width(self.element.width());
+    }
+
+    fn draw(self: &Rc<Self>) -> crate::Result<()> {
+        self.reset();
+
+        let drawing = self.drawing.borrow();
+        let mut used_images = vec![];
+        for command in drawing.commands() {
+            if let Command::DrawImage { src, x, y, size } = command {
+                used_images.push(src.clone());
+                if let Some(image) = self.image(src)? {
+                    let result = match size {
+                        Some((width, height)) => self
+                            .context
+                            .draw_image_with_html_image_element_and_dw_and_dh(
+                                &image, *x, *y, *width, *height,
+                            ),
+                        None => self
+                            .context
+                            .draw_image_with_html_image_element(&image, *x, *y),
+                    };
+                    result.map_err(crate::Error::dom("CanvasRenderingContext2D.drawImage"))?;
+                }
+                continue;
+            }
+            self.apply(command)?;
+        }
+
+        // Forget the images that are no longer drawn.
+        self.images
+            .borrow_mut()
+            .retain(|src, _| used_images.contains(src));
+
+        Ok(())
+    }
+
+    /// Return the image at the URL if it has been loaded.
+    ///
+    /// The canvas is redrawn when the image being loaded is completed.
+    fn image(self: &Rc<Self>, src: &CowStr) -> crate::Result<Option<web::HtmlImageElement>> {
+        let mut images = self.images.borrow_mut();
+        if let Some(image) = images.get(src) {
+            let element = &image.element;
+            return Ok(if element.complete() && element.natural_width() > 0 {
+                Some(element.clone())
+            } else {
+                None
+            });
+        }
+
+        let element =
+            web::HtmlImageElement::new().map_err(crate::Error::dom("HTMLImageElement"))?;
+        let on_load = EventListener::once(&element, "load", {
+            let inner: Weak<Self> = Rc::downgrade(self);
+            move |_| {
+                if let Some(inner) = inner.upgrade() {
+                    if let Err(err) = inner.draw() {
+                        web::console::error_1(&err.into());
+                    }
+                }
+            }
+        });
+        element.set_src(src);
+        images.insert(
+            src.clone(),
+            Image {
+                element,
+                _on_load: on_load,
+            },
+        );
+
+        Ok(None)
+    }
+
+    fn apply(&self, command: &Command) -> crate::Result<()> {
+        let ctx = &self.context;
+        match command {
+            Command::Save => ctx.save(),
+            Command::Restore => ctx.restore(),
+
+            Command::BeginPath => ctx.begin_path(),
+            Command::ClosePath => ctx.close_path(),
+            Command::MoveTo { x, y } => ctx.move_to(*x, *y),
+            Command::LineTo { x, y } => ctx.line_to(*x, *y),
+            Command::QuadraticCurveTo { cpx, cpy, x, y } => {
+                ctx.quadratic_curve_to(*cpx, *cpy, *x, *y)
+            }
+            Command::BezierCurveTo {
+                cp1x,
+                cp1y,
+                cp2x,
+                cp2y,
+                x,
+                y,
+            } => ctx.bezier_curve_to(*cp1x, *cp1y, *cp2x, *cp2y, *x, *y),
+            Command::Arc {
+                x,
+                y,
+                radius,
+                start_angle,
+                end_angle,
+                anticlockwise,
+            } => ctx
+                .arc_with_anticlockwise(*x, *y, *radius, *start_angle, *end_angle, *anticlockwise)
+                .map_err(crate::Error::dom("CanvasRenderingContext2D.arc"))?,
+            Command::Rect {
+                x,
+                y,
+                width,
+                height,
+            } => ctx.rect(*x, *y, *width, *height),
+            Command::Fill => ctx.fill(),
+            Command::Stroke => ctx.stroke(),
+            Command::Clip => ctx.clip(),
+
+            Command::FillRect {
+                x,
+                y,
+                width,
+                height,
+            } => ctx.fill_rect(*x, *y, *width, *height),
+            Command::StrokeRect {
+                x,
+                y,
+                width,
+                height,
+            } => ctx.stroke_rect(*x, *y, *width, *height),
+            Command::ClearRect {
+                x,
+                y,
+                width,
+                height,
+            } => ctx.clear_rect(*x, *y, *width, *height),
+
+            Command::FillText { text, x, y } => ctx
+                .fill_text(text, *x, *y)
+                .map_err(crate::Error::dom("CanvasRenderingContext2D.fillText"))?,
+            Command::StrokeText { text, x, y } => ctx
+                .stroke_text(text, *x, *y)
+                .map_err(crate::Error::dom("CanvasRenderingContext2D.strokeText"))?,
+
+            Command::Translate { x, y } => ctx
+                .translate(*x, *y)
+                .map_err(crate::Error::dom("CanvasRenderingContext2D.translate"))?,
+            Command::Rotate { angle } => ctx
+                .rotate(*angle)
+                .map_err(crate::Error::dom("CanvasRenderingContext2D.rotate"))?,
+            Command::Scale { x, y } => ctx
+                .scale(*x, *y)
+                .map_err(crate::Error::dom("CanvasRenderingContext2D.scale"))?,
+            Command::Transform { a, b, c, d, e, f } => ctx
+                .transform(*a, *b, *c, *d, *e, *f)
+                .map_err(crate::Error::dom("CanvasRenderingContext2D.transform"))?,
+            Command::ResetTransform => ctx
+                .set_transform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+                .map_err(crate::Error::dom("CanvasRenderingContext2D.setTransform"))?,
+
+            Command::FillStyle(style) => ctx.set_fill_style_str(style),
+            Command::StrokeStyle(style) => ctx.set_stroke_style_str(style),
+            Command::LineWidth(width) => ctx.set_line_width(*width),
+            Command::LineCap(cap) => ctx.set_line_cap(cap),
+            Command::LineJoin(join) => ctx.set_line_join(join),
+            Command::LineDash(segments) => {
+                let segments: js_sys::Array = segments.iter().copied().map(JsValue::from).collect();
+                ctx.set_line_dash(&segments)
+                    .map_err(crate::Error::dom("CanvasRenderingContext2D.setLineDash"))?
+            }
+            Command::Font(font) => ctx.set_font(font),
+            Command::TextAlign(align) => ctx.set_text_align(align),
+            Command::TextBaseline(baseline) => ctx.set_text_baseline(baseline),
+            Command::GlobalAlpha(alpha) => ctx.set_global_alpha(*alpha),
+
+            // Images are drawn in `draw`, and the unknown commands are ignored.
+            _ => (),
+        }
+        Ok(())
+    }
+}
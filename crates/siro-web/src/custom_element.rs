@@ -0,0 +1,252 @@
+use crate::{
+    app::{App, DropBehavior},
+    env::Env,
+};
+use futures::{
+    channel::mpsc,
+    future::{AbortHandle, Abortable},
+    prelude::*,
+};
+use once_cell::unsync::OnceCell;
+use std::{borrow::Cow, cell::RefCell, rc::Rc};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(module = "/js/custom_element.js")]
+extern "C" {
+    /// Define the custom element class whose lifecycle callbacks call the closures.
+    #[wasm_bindgen(catch, js_name = defineCustomElement)]
+    fn define_custom_element(
+        name: &str,
+        observed_attributes: js_sys::Array,
+        connected: &JsValue,
+        disconnected: &JsValue,
+        attribute_changed: &JsValue,
+    ) -> Result<(), JsValue>;
+}
+
+/// Return the environment shared by the instances of all custom elements.
+///
+/// The definitions of custom elements are never removed, so the environment
+/// is created once and lives forever.
+fn shared_env() -> crate::Result<&'static Env> {
+    thread_local! {
+        static ENV: OnceCell<&'static Env> = const { OnceCell::new() };
+    }
+    ENV.with(|env| {
+        env.get_or_try_init(|| Ok(&*Box::leak(Box::new(Env::new()?))))
+            .copied()
+    })
+}
+
+type AttributeFn<TMsg> = Rc<dyn Fn(Option<String>) -> TMsg>;
+
+/// The definition of a custom element whose instances run their own `App`.
+///
+/// Each instance mounts an application into its shadow root when it is
+/// connected to the document, and the application is stopped and unmounted
+/// when the instance is removed.
+///
+/// ```ignore
+/// CustomElement::new("my-counter")
+///     .attribute("value", |value| Msg::SetValue(value))
+///     .define(|app| siro_web::run_app(Counter, app))?;
+/// ```
+pub struct CustomElement<TMsg: 'static> {
+    name: Cow<'static, str>,
+    attributes: Vec<(Cow<'static, str>, AttributeFn<TMsg>)>,
+}
+
+impl<TMsg: 'static> CustomElement<TMsg> {
+    /// Start the definition of a custom element with the tag name, e.g. `"my-widget"`.
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            name: name.into(),
+            attributes: vec![],
+        }
+    }
+
+    /// Observe the attribute, and send the message created from its value to
+    /// the application when it is changed.
+    ///
+    /// The message is also sent when the instance is connected, if the
+    /// attribute is present at that time.
+    pub fn attribute<F>(mut self, name: impl Into<Cow<'static, str>>, f: F) -> Self
+    where
+        F: Fn(Option<String>) -> TMsg + 'static,
+    {
+        self.attributes.push((name.into(), Rc::new(f)));
+        self
+    }
+
+    /// Register the custom element to the document.
+    ///
+    /// The function is called with the mounted `App` each time an instance is
+    /// connected, and the returned future drives the application.  The future
+    /// is dropped when the instance is removed from the document.
+    pub fn define<F, Fut>(self, f: F) -> crate::Result<()>
+    where
+        F: Fn(App<'static, TMsg>) -> Fut + 'static,
+        Fut: Future<Output = crate::Result<()>> + 'static,
+    {
+        let registry = Rc::new(Registry {
+            env: shared_env()?,
+            attributes: self.attributes,
+            start: Box::new(move |app| f(app).boxed_local()),
+            instances: RefCell::new(vec![]),
+        });
+
+        let connected = Closure::wrap(Box::new({
+            let registry = registry.clone();
+            move |element: web::HtmlElement| {
+                if let Err(err) = registry.connect(element) {
+                    web::console::error_1(&err.into());
+                }
+            }
+        }) as Box<dyn FnMut(web::HtmlElement)>);
+
+        let disconnected = Closure::wrap(Box::new({
+            let registry = registry.clone();
+            move |element: web::HtmlElement| registry.disconnect(element)
+        }) as Box<dyn FnMut(web::HtmlElement)>);
+
+        let attribute_changed = Closure::wrap(Box::new({
+            let registry = registry.clone();
+            move |element: web::HtmlElement, name: String, value: Option<String>| {
+                registry.attribute_changed(&element, &name, value)
+            }
+        })
+            as Box<dyn FnMut(web::HtmlElement, String, Option<String>)>);
+
+        let observed_attributes = registry
+            .attributes
+            .iter()
+            .map(|(name, _)| JsValue::from_str(name))
+            .collect();
+
+        define_custom_element(
+            &self.name,
+            observed_attributes,
+            connected.as_ref(),
+            disconnected.as_ref(),
+            attribute_changed.as_ref(),
+        )
+        .map_err(crate::Error::dom("CustomElementRegistry.define"))?;
+
+        // The callbacks are referenced by the class as long as the page is alive.
+        connected.forget();
+        disconnected.forget();
+        attribute_changed.forget();
+
+        Ok(())
+    }
+}
+
+type Start<TMsg> =
+    Box<dyn Fn(App<'static, TMsg>) -> future::LocalBoxFuture<'static, crate::Result<()>>>;
+
+struct Registry<TMsg: 'static> {
+    env: &'static Env,
+    attributes: Vec<(Cow<'static, str>, AttributeFn<TMsg>)>,
+    start: Start<TMsg>,
+    instances: RefCell<Vec<Instance<TMsg>>>,
+}
+
+impl<TMsg: 'static> Registry<TMsg> {
+    fn connect(self: &Rc<Self>, element: web::HtmlElement) -> crate::Result<()> {
+        // The instance moved within the document is kept running.
+        if self.find(&element).is_some() {
+            return Ok(());
+        }
+
+        let shadow_root = match element.shadow_root() {
+            Some(shadow_root) => shadow_root,
+            None => element
+                .attach_shadow(&web::ShadowRootInit::new(web::ShadowRootMode::Open))
+                .map_err(crate::Error::dom("Element.attachShadow"))?,
+        };
+
+        // Each application is mounted on its own container, so that the
+        // container can be removed at once when the instance is stopped.
+        let container = self
+            .env
+            .document
+            .create_element("div")
+            .map_err(crate::Error::dom("Document.createElement"))?;
+        shadow_root
+            .append_child(&container)
+            .map_err(crate::Error::dom("Node.appendChild"))?;
+
+        let mut app = App::new(self.env, container.clone().into())?;
+        app.set_host(element.clone().into());
+        app.set_drop_behavior(DropBehavior::Unmount);
+        let tx = app.sender();
+
+        for (name, f) in &self.attributes {
+            if let Some(value) = element.get_attribute(name) {
+                let _ = tx.unbounded_send(f(Some(value)));
+            }
+        }
+
+        let (handle, registration) = AbortHandle::new_pair();
+        let task = Abortable::new((self.start)(app), registration);
+        wasm_bindgen_futures::spawn_local(task.map(|result| {
+            if let Ok(Err(err)) = result {
+                web::console::error_1(&err.into());
+            }
+        }));
+
+        self.instances.borrow_mut().push(Instance {
+            element,
+            container,
+            tx,
+            handle,
+        });
+
+        Ok(())
+    }
+
+    fn disconnect(self: &Rc<Self>, element: web::HtmlElement) {
+        // The instance is stopped after the current task, unless it has been
+        // reconnected in the meantime, e.g. by moving it to another parent.
+        let registry = self.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            if !element.is_connected() {
+                if let Some(i) = registry.find(&element) {
+                    registry.instances.borrow_mut().remove(i);
+                }
+            }
+        });
+    }
+
+    fn attribute_changed(&self, element: &web::HtmlElement, name: &str, value: Option<String>) {
+        let f = match self.attributes.iter().find(|(n, _)| n == name) {
+            Some((_, f)) => f,
+            None => return,
+        };
+        if let Some(i) = self.find(element) {
+            let _ = self.instances.borrow()[i].tx.unbounded_send(f(value));
+        }
+    }
+
+    fn find(&self, element: &web::HtmlElement) -> Option<usize> {
+        self.instances
+            .borrow()
+            .iter()
+            .position(|instance| instance.element.is_same_node(Some(element)))
+    }
+}
+
+/// A running instance of the custom element, which is stopped on drop.
+struct Instance<TMsg> {
+    element: web::HtmlElement,
+    container: web::Element,
+    tx: mpsc::UnboundedSender<TMsg>,
+    handle: AbortHandle,
+}
+
+impl<TMsg> Drop for Instance<TMsg> {
+    fn drop(&mut self) {
+        self.handle.abort();
+        self.container.remove();
+    }
+}
@@ -0,0 +1,528 @@
+//! A debug runtime for `Program`s with the time-travel debugger.
+//!
+//! The runtime records every message applied to the program along with the
+//! snapshot of the model, and renders a panel to scrub through the history
+//! next to the view of the program.  The recorded session can be exported
+//! as JSON, e.g. to attach it to bug reports, and imported again later.
+
+use crate::{
+    app::App,
+    effects::Effects,
+    env::Env,
+    program::{drive, Runtime, View},
+    subscription::{Map, Subscription},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use siro::{
+    effects::{Clipboard, Console, DocumentTitle, DomFocus, Navigation, Scroll, Storage, Timers},
+    html::{button, div, event::on_click, event::on_input, input, li, ol, span, textarea},
+    vdom::{attribute, iter, property, style, Nodes},
+    Cmd, Program,
+};
+use std::collections::VecDeque;
+
+/// The options of the debug runtime.
+#[derive(Debug, Clone)]
+pub struct Options {
+    capacity: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self { capacity: 100 }
+    }
+}
+
+impl Options {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Specify the maximum number of messages kept in the history.
+    ///
+    /// When the history is full, the oldest message is forgotten and its
+    /// resulting model becomes the initial state of the history.
+    pub fn capacity(self, capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+        }
+    }
+}
+
+/// Run a `Program` with the time-travel debugger on the DOM node specified by the selector.
+///
+/// This function behaves like `run`, except that the messages and the model
+/// snapshots are recorded and the debugger panel is rendered after the view.
+/// While a past state is selected on the panel, the view of the program
+/// shows that state and does not react to user inputs, and the messages
+/// from subscriptions and commands are still applied to the latest model.
+///
+/// The commands returned and the effects requested while replaying an
+/// imported session are discarded.
+pub async fn run<P>(program: P, selector: &str, options: Options) -> crate::Result<()>
+where
+    P: Program,
+    P::Model: Clone + Serialize + DeserializeOwned,
+    P::Msg: Serialize + DeserializeOwned,
+    P::Subscriptions: Subscription<Msg = P::Msg> + Clone + PartialEq,
+{
+    let env = Env::new()?;
+    let app = env.mount(selector)?;
+    drive(Debugger::new(&program, options), app).await
+}
+
+// ==== Debugger ====
+
+enum Msg<TMsg> {
+    App(TMsg),
+    Jump(usize),
+    Scrub(String),
+    Back,
+    Forward,
+    Live,
+    Export,
+    Edit(String),
+    Import,
+}
+
+/// The messages sent by `Timers` of the program are wrapped into `Msg::App`.
+impl<TMsg> From<TMsg> for Msg<TMsg> {
+    fn from(msg: TMsg) -> Self {
+        Msg::App(msg)
+    }
+}
+
+struct Debugger<'a, P: Program> {
+    program: &'a P,
+    model: P::Model,
+    history: History<P::Model>,
+    session: String,
+    status: Option<String>,
+}
+
+impl<'a, P> Debugger<'a, P>
+where
+    P: Program,
+    P::Model: Clone + Serialize + DeserializeOwned,
+    P::Msg: Serialize + DeserializeOwned,
+{
+    fn new(program: &'a P, options: Options) -> Self {
+        let model = program.init();
+        Self {
+            program,
+            history: History::new(model.clone(), options.capacity),
+            model,
+            session: String::new(),
+            status: None,
+        }
+    }
+
+    fn update(
+        &mut self,
+        msg: Msg<P::Msg>,
+        effects: &mut Effects<'_, Msg<P::Msg>>,
+    ) -> Cmd<Msg<P::Msg>> {
+        match msg {
+            Msg::App(msg) => {
+                // The messages that cannot be serialized are recorded as their error messages.
+                let recorded = serde_json::to_value(&msg)
+                    .unwrap_or_else(|err| serde_json::Value::String(err.to_string()));
+                let cmd = self.program.update(&mut self.model, msg, effects);
+                self.history.record(recorded, self.model.clone());
+                return cmd.map(Msg::App);
+            }
+            Msg::Jump(position) => self.history.jump(position),
+            Msg::Scrub(value) => {
+                if let Ok(position) = value.parse() {
+                    self.history.jump(position);
+                }
+            }
+            Msg::Back => self.history.jump(self.history.position().saturating_sub(1)),
+            Msg::Forward => self.history.jump(self.history.position() + 1),
+            Msg::Live => self.history.jump(self.history.len()),
+            Msg::Export => match self.history.export() {
+                Ok(session) => {
+                    self.session = session;
+                    self.status = None;
+                }
+                Err(err) => self.status = Some(format!("failed to export: {}", err)),
+            },
+            Msg::Edit(session) => self.session = session,
+            Msg::Import => match self.history.import(self.program, &self.session) {
+                Ok(model) => {
+                    self.model = model;
+                    self.status = Some(format!("imported {} messages", self.history.len()));
+                }
+                Err(err) => self.status = Some(format!("failed to import: {}", err)),
+            },
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> impl Nodes<Msg<P::Msg>> + '_ {
+        let selected = self.history.selected();
+        (
+            div(
+                selected.map(|_| (style("pointer-events", "none"), style("opacity", "0.6"))),
+                View {
+                    program: self.program,
+                    model: selected.unwrap_or(&self.model),
+                }
+                .map(Msg::App),
+            ),
+            self.view_panel(),
+        )
+    }
+
+    fn view_panel(&self) -> impl Nodes<Msg<P::Msg>> + '_ {
+        let position = self.history.position();
+        let len = self.history.len();
+        div(
+            (
+                style("position", "fixed"),
+                style("right", "0"),
+                style("bottom", "0"),
+                style("width", "360px"),
+                style("max-height", "50vh"),
+                style("overflow", "auto"),
+                style("padding", "8px"),
+                style("background", "rgba(32, 32, 32, 0.9)"),
+                style("color", "#eee"),
+                style("font", "12px monospace"),
+                style("z-index", "2147483647"),
+            ),
+            (
+                div(
+                    (),
+                    (
+                        span((), format!("{} / {} ", position, len)),
+                        button(on_click(|| Msg::Back), "<"),
+                        button(on_click(|| Msg::Forward), ">"),
+                        button(on_click(|| Msg::Live), "Live"),
+                    ),
+                ),
+                input::range((
+                    attribute("min", "0"),
+                    attribute("max", len.to_string()),
+                    property("value", position.to_string()),
+                    style("width", "100%"),
+                    on_input(Msg::Scrub),
+                )),
+                ol(
+                    attribute("start", "1"),
+                    iter(self.history.messages().enumerate().map(move |(i, msg)| {
+                        li(
+                            (
+                                style("cursor", "pointer"),
+                                (i + 1 == position).then(|| style("font-weight", "bold")),
+                                on_click(move || Msg::Jump(i + 1)),
+                            ),
+                            msg.to_string(),
+                        )
+                    })),
+                ),
+                textarea(
+                    (
+                        attribute("rows", "4"),
+                        attribute("placeholder", "Session JSON"),
+                        property("value", self.session.clone()),
+                        style("width", "100%"),
+                        on_input(Msg::Edit),
+                    ),
+                    (),
+                ),
+                div(
+                    (),
+                    (
+                        button(on_click(|| Msg::Export), "Export"),
+                        button(on_click(|| Msg::Import), "Import"),
+                        self.status.clone().map(|status| span((), (" ", status))),
+                    ),
+                ),
+            ),
+        )
+    }
+}
+
+impl<'a, P> Runtime for Debugger<'a, P>
+where
+    P: Program,
+    P::Model: Clone + Serialize + DeserializeOwned,
+    P::Msg: Serialize + DeserializeOwned,
+    P::Subscriptions: Subscription<Msg = P::Msg> + Clone + PartialEq,
+{
+    type Msg = Msg<P::Msg>;
+    type Subscriptions = Map<P::Subscriptions, fn(P::Msg) -> Msg<P::Msg>>;
+
+    fn update(&mut self, msg: Self::Msg, effects: &mut Effects<'_, Self::Msg>) -> Cmd<Self::Msg> {
+        Debugger::update(self, msg, effects)
+    }
+
+    fn render(&self, app: &mut App<'_, Self::Msg>) -> crate::Result<()> {
+        app.render(self.view())
+    }
+
+    fn subscriptions(&self) -> Self::Subscriptions {
+        // The subscriptions always follow the latest model, even while a past
+        // state is selected.
+        self.program.subscriptions(&self.model).map(Msg::App)
+    }
+}
+
+// ==== History ====
+
+/// The bounded history of the messages and the models resulting from them.
+struct History<TModel> {
+    capacity: usize,
+    initial: TModel,
+    entries: VecDeque<Entry<TModel>>,
+    /// The number of messages applied to the selected state, or `None` if
+    /// the latest state is selected.
+    cursor: Option<usize>,
+}
+
+struct Entry<TModel> {
+    msg: serde_json::Value,
+    model: TModel,
+}
+
+/// The serialized form of a recorded session.
+#[derive(Serialize, Deserialize)]
+struct Session<TModel, TMsg> {
+    initial: TModel,
+    messages: Vec<TMsg>,
+}
+
+impl<TModel> History<TModel> {
+    fn new(initial: TModel, capacity: usize) -> Self {
+        Self {
+            capacity,
+            initial,
+            entries: VecDeque::new(),
+            cursor: None,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn position(&self) -> usize {
+        self.cursor.unwrap_or_else(|| self.len())
+    }
+
+    fn messages(&self) -> impl Iterator<Item = &serde_json::Value> + '_ {
+        self.entries.iter().map(|entry| &entry.msg)
+    }
+
+    /// Return the selected state, or `None` if the latest one is selected.
+    fn selected(&self) -> Option<&TModel> {
+        match self.cursor? {
+            0 => Some(&self.initial),
+            n => Some(&self.entries[n - 1].model),
+        }
+    }
+
+    fn jump(&mut self, position: usize) {
+        self.cursor = Some(position).filter(|&position| position < self.len());
+    }
+
+    fn record(&mut self, msg: serde_json::Value, model: TModel) {
+        self.entries.push_back(Entry { msg, model });
+        if self.entries.len() > self.capacity {
+            if let Some(oldest) = self.entries.pop_front() {
+                self.initial = oldest.model;
+            }
+            // Keep selecting the same state as far as it remains.
+            if let Some(ref mut cursor) = self.cursor {
+                *cursor = cursor.saturating_sub(1);
+            }
+        }
+    }
+
+    fn export(&self) -> serde_json::Result<String>
+    where
+        TModel: Serialize,
+    {
+        serde_json::to_string(&Session {
+            initial: &self.initial,
+            messages: self.messages().collect::<Vec<_>>(),
+        })
+    }
+
+    /// Replace the history with the session by replaying its messages,
+    /// and return the latest model.
+    fn import<P>(&mut self, program: &P, session: &str) -> serde_json::Result<TModel>
+    where
+        P: Program<Model = TModel>,
+        P::Msg: DeserializeOwned,
+        TModel: Clone + DeserializeOwned,
+    {
+        let session: Session<TModel, serde_json::Value> = serde_json::from_str(session)?;
+
+        let mut model = session.initial.clone();
+        let mut history = History::new(session.initial, self.capacity);
+        for recorded in session.messages {
+            let msg = P::Msg::deserialize(&recorded)?;
+            let _ = program.update(&mut model, msg, &mut Discard);
+            history.record(recorded, model.clone());
+        }
+
+        *self = history;
+        Ok(model)
+    }
+}
+
+/// The effects that do nothing, used while replaying an imported session.
+struct Discard;
+
+impl siro::effects::Effects for Discard {
+    type Ok = ();
+    type Error = std::convert::Infallible;
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl DomFocus for Discard {}
+impl Navigation for Discard {}
+impl Storage for Discard {}
+impl<TMsg> Timers<TMsg> for Discard {}
+impl Scroll for Discard {}
+impl Clipboard for Discard {}
+impl Console for Discard {}
+impl DocumentTitle for Discard {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use siro::{effects::AllEffects, vdom::NodesRenderer};
+
+    /// A program whose model is the sum of the messages.
+    struct Sum;
+
+    impl Program for Sum {
+        type Model = i32;
+        type Msg = i32;
+        type Subscriptions = ();
+
+        fn init(&self) -> i32 {
+            0
+        }
+
+        fn update<E>(&self, model: &mut i32, msg: i32, _: &mut E) -> Cmd<i32>
+        where
+            E: AllEffects<i32>,
+        {
+            *model += msg;
+            Cmd::none()
+        }
+
+        fn view<R>(&self, _: &i32, renderer: R) -> Result<R::Ok, R::Error>
+        where
+            R: NodesRenderer<Msg = i32>,
+        {
+            ().render_nodes(renderer)
+        }
+
+        fn subscriptions(&self, _: &i32) {}
+    }
+
+    fn record(history: &mut History<i32>, model: &mut i32, msg: i32) {
+        *model += msg;
+        history.record(msg.into(), *model);
+    }
+
+    fn messages(history: &History<i32>) -> Vec<i64> {
+        history.messages().filter_map(|msg| msg.as_i64()).collect()
+    }
+
+    #[test]
+    fn jump_selects_past_states() {
+        let mut history = History::new(0, 10);
+        let mut model = 0;
+        for msg in 1..=3 {
+            record(&mut history, &mut model, msg);
+        }
+        assert_eq!(history.selected(), None);
+        assert_eq!(history.position(), 3);
+
+        history.jump(0);
+        assert_eq!(history.selected(), Some(&0));
+        history.jump(2);
+        assert_eq!(history.selected(), Some(&3));
+        assert_eq!(history.position(), 2);
+
+        // Jumping to the end selects the latest state again.
+        history.jump(3);
+        assert_eq!(history.selected(), None);
+        history.jump(100);
+        assert_eq!(history.selected(), None);
+    }
+
+    #[test]
+    fn eviction_keeps_selected_state() {
+        let mut history = History::new(0, 2);
+        let mut model = 0;
+        record(&mut history, &mut model, 1);
+        record(&mut history, &mut model, 2);
+
+        history.jump(1);
+        assert_eq!(history.selected(), Some(&1));
+
+        record(&mut history, &mut model, 3);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.position(), 0);
+        assert_eq!(history.selected(), Some(&1));
+        assert_eq!(messages(&history), [2, 3]);
+
+        // The selected state itself is evicted, so the oldest remaining one is selected.
+        record(&mut history, &mut model, 4);
+        assert_eq!(history.position(), 0);
+        assert_eq!(history.selected(), Some(&3));
+    }
+
+    #[test]
+    fn eviction_while_live() {
+        let mut history = History::new(0, 2);
+        let mut model = 0;
+        for msg in 1..=4 {
+            record(&mut history, &mut model, msg);
+        }
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.selected(), None);
+        history.jump(0);
+        assert_eq!(history.selected(), Some(&3));
+    }
+
+    #[test]
+    fn export_import_round_trip() {
+        let mut history = History::new(0, 2);
+        let mut model = 0;
+        for msg in 1..=3 {
+            record(&mut history, &mut model, msg);
+        }
+        let session = history.export().unwrap();
+
+        let mut imported = History::new(0, 10);
+        imported.jump(0);
+        let model = imported.import(&Sum, &session).unwrap();
+        assert_eq!(model, 6);
+        assert_eq!(imported.len(), 2);
+        assert_eq!(imported.selected(), None);
+        assert_eq!(messages(&imported), [2, 3]);
+        imported.jump(0);
+        assert_eq!(imported.selected(), Some(&1));
+        assert_eq!(imported.export().unwrap(), session);
+    }
+
+    #[test]
+    fn import_rejects_invalid_messages() {
+        let mut history = History::new(0, 10);
+        assert!(history
+            .import(&Sum, r#"{"initial":0,"messages":["a"]}"#)
+            .is_err());
+        assert!(history.import(&Sum, "{").is_err());
+    }
+}
@@ -0,0 +1,303 @@
+use crate::{env::Env, error::ErrorKind};
+use futures::channel::mpsc;
+use serde::{de::DeserializeOwned, Serialize};
+use siro::effects::{
+    Clipboard, Console, DocumentTitle, DomFocus, Navigation, Scroll, Storage, Timers,
+};
+use std::{convert::TryFrom, time::Duration};
+use wasm_bindgen::prelude::*;
+
+/// The implementation of managed effects on the browser.
+///
+/// The DOM operations that depend on the rendered nodes, such as focusing
+/// or scrolling, are deferred until the next call of `App::render`.
+pub struct Effects<'a, TMsg: 'static> {
+    pub(crate) env: &'a Env,
+    pub(crate) tx: &'a mpsc::UnboundedSender<TMsg>,
+    pub(crate) host: &'a web::EventTarget,
+    pub(crate) deferred: &'a mut Vec<Deferred>,
+}
+
+impl<TMsg: 'static> Effects<'_, TMsg> {
+    /// Dispatch a `CustomEvent` with the detail on the host of this application.
+    ///
+    /// The host is the custom element if the application runs inside it, or
+    /// the mountpoint otherwise.  The event bubbles and crosses the boundary
+    /// of shadow roots, so that it can be listened by the embedding page.
+    pub fn dispatch_event<T>(&mut self, event_type: &str, detail: &T) -> crate::Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let detail = serde_wasm_bindgen::to_value(&detail).map_err(|err| {
+            crate::Error::custom("failed to serialize the event detail").with_js_value(err.into())
+        })?;
+
+        let init = web::CustomEventInit::new();
+        init.set_bubbles(true);
+        init.set_composed(true);
+        init.set_detail(&detail);
+
+        let event = web::CustomEvent::new_with_event_init_dict(event_type, &init)
+            .map_err(crate::Error::dom("new CustomEvent"))?;
+        self.host
+            .dispatch_event(&event)
+            .map_err(crate::Error::dom("EventTarget.dispatchEvent"))?;
+
+        Ok(())
+    }
+}
+
+impl<TMsg: 'static> siro::effects::Effects for Effects<'_, TMsg> {
+    type Ok = ();
+    type Error = crate::Error;
+
+    fn end(self) -> crate::Result<()> {
+        Ok(())
+    }
+}
+
+impl<TMsg: 'static> DomFocus for Effects<'_, TMsg> {
+    fn focus(&mut self, target_id: &str) -> crate::Result<()> {
+        self.deferred.push(Deferred::Focus(target_id.to_owned()));
+        Ok(())
+    }
+
+    fn blur(&mut self, target_id: &str) -> crate::Result<()> {
+        self.deferred.push(Deferred::Blur(target_id.to_owned()));
+        Ok(())
+    }
+}
+
+impl<TMsg: 'static> Navigation for Effects<'_, TMsg> {
+    fn push_url(&mut self, url: &str) -> crate::Result<()> {
+        push_url(&self.env.window, url)
+    }
+
+    fn replace_url(&mut self, url: &str) -> crate::Result<()> {
+        let history = self.history()?;
+        history
+            .replace_state_with_url(&JsValue::NULL, "", Some(url))
+            .map_err(crate::Error::dom("History.replaceState"))?;
+        notify_url_change(&self.env.window)
+    }
+
+    fn back(&mut self, count: usize) -> crate::Result<()> {
+        let delta = i32::try_from(count).unwrap_or(i32::MAX);
+        self.history()?
+            .go_with_delta(-delta)
+            .map_err(crate::Error::dom("History.go"))
+    }
+
+    fn forward(&mut self, count: usize) -> crate::Result<()> {
+        let delta = i32::try_from(count).unwrap_or(i32::MAX);
+        self.history()?
+            .go_with_delta(delta)
+            .map_err(crate::Error::dom("History.go"))
+    }
+
+    fn load(&mut self, url: &str) -> crate::Result<()> {
+        self.env
+            .window
+            .location()
+            .assign(url)
+            .map_err(crate::Error::dom("Location.assign"))
+    }
+
+    fn reload(&mut self, skip_cache: bool) -> crate::Result<()> {
+        self.env
+            .window
+            .location()
+            .reload_with_forceget(skip_cache)
+            .map_err(crate::Error::dom("Location.reload"))
+    }
+}
+
+impl<TMsg: 'static> Effects<'_, TMsg> {
+    fn history(&self) -> crate::Result<web::History> {
+        self.env
+            .window
+            .history()
+            .map_err(crate::Error::dom("Window.history"))
+    }
+}
+
+pub(crate) fn push_url(window: &web::Window, url: &str) -> crate::Result<()> {
+    window
+        .history()
+        .map_err(crate::Error::dom("Window.history"))?
+        .push_state_with_url(&JsValue::NULL, "", Some(url))
+        .map_err(crate::Error::dom("History.pushState"))?;
+    notify_url_change(window)
+}
+
+/// Notify the URL change to the subscriptions, since `pushState` and
+/// `replaceState` do not fire `popstate`.
+///
+/// A namespaced event is used so that the code outside of the application
+/// does not mistake it for a history traversal.
+fn notify_url_change(window: &web::Window) -> crate::Result<()> {
+    let event = web::Event::new(crate::subscription::URL_CHANGE_EVENT)
+        .map_err(crate::Error::dom("new Event"))?;
+    window
+        .dispatch_event(&event)
+        .map_err(crate::Error::dom("EventTarget.dispatchEvent"))?;
+    Ok(())
+}
+
+impl<TMsg: 'static> Storage for Effects<'_, TMsg> {
+    fn get_item<T>(&mut self, key: &str) -> crate::Result<Option<T>>
+    where
+        T: DeserializeOwned,
+    {
+        match self.env.get_storage_item(key)? {
+            Some(encoded) => crate::storage::decode(&encoded).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn set_item<T>(&mut self, key: &str, value: &T) -> crate::Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let encoded = serde_json::to_string(value).map_err(|err| {
+            crate::Error::custom("failed to encode the storage item").with_source(err)
+        })?;
+        self.env.set_storage_item(key, encoded)
+    }
+
+    fn remove_item(&mut self, key: &str) -> crate::Result<()> {
+        self.env.remove_storage_item(key)
+    }
+}
+
+/// The messages are converted into `TMsg`, so that the effects can be
+/// passed to a program whose messages are wrapped by the runtime.
+impl<TMsg: 'static, UMsg> Timers<UMsg> for Effects<'_, TMsg>
+where
+    UMsg: Into<TMsg>,
+{
+    fn after(&mut self, duration: Duration, msg: UMsg) -> crate::Result<()> {
+        let msg = msg.into();
+        let tx = self.tx.clone();
+        let cb = Closure::once_into_js(move || {
+            let _ = tx.unbounded_send(msg);
+        });
+        let timeout = i32::try_from(duration.as_millis()).unwrap_or(i32::MAX);
+        self.env
+            .window
+            .set_timeout_with_callback_and_timeout_and_arguments_0(cb.unchecked_ref(), timeout)
+            .map_err(crate::Error::dom("setTimeout"))?;
+        Ok(())
+    }
+}
+
+impl<TMsg: 'static> Scroll for Effects<'_, TMsg> {
+    fn scroll_into_view(&mut self, target_id: &str) -> crate::Result<()> {
+        self.deferred
+            .push(Deferred::ScrollIntoView(target_id.to_owned()));
+        Ok(())
+    }
+
+    fn scroll_to(&mut self, x: f64, y: f64) -> crate::Result<()> {
+        self.deferred.push(Deferred::ScrollTo(x, y));
+        Ok(())
+    }
+}
+
+impl<TMsg: 'static> Clipboard for Effects<'_, TMsg> {
+    fn write_text(&mut self, text: &str) -> crate::Result<()> {
+        // `navigator.clipboard` is accessed dynamically since it is not
+        // available in insecure contexts.
+        let navigator = self.env.window.navigator();
+        let clipboard = js_sys::Reflect::get(&navigator, &JsValue::from_str("clipboard"))
+            .map_err(crate::Error::dom("navigator.clipboard"))?;
+        if clipboard.is_undefined() {
+            return Err(crate::Error::new(ErrorKind::Unsupported(
+                "navigator.clipboard",
+            )));
+        }
+        let write_text = js_sys::Reflect::get(&clipboard, &JsValue::from_str("writeText"))
+            .map_err(crate::Error::dom("navigator.clipboard"))?;
+        write_text
+            .unchecked_ref::<js_sys::Function>()
+            .call1(&clipboard, &JsValue::from_str(text))
+            .map_err(crate::Error::dom("Clipboard.writeText"))?;
+        Ok(())
+    }
+}
+
+impl<TMsg: 'static> Console for Effects<'_, TMsg> {
+    fn log(&mut self, message: &str) -> crate::Result<()> {
+        web::console::log_1(&JsValue::from_str(message));
+        Ok(())
+    }
+
+    fn warn(&mut self, message: &str) -> crate::Result<()> {
+        web::console::warn_1(&JsValue::from_str(message));
+        Ok(())
+    }
+
+    fn error(&mut self, message: &str) -> crate::Result<()> {
+        web::console::error_1(&JsValue::from_str(message));
+        Ok(())
+    }
+}
+
+impl<TMsg: 'static> DocumentTitle for Effects<'_, TMsg> {
+    fn set_title(&mut self, title: &str) -> crate::Result<()> {
+        self.env.document.set_title(title);
+        Ok(())
+    }
+}
+
+// ==== Deferred ====
+
+/// A DOM operation applied after rendering.
+#[derive(Debug)]
+pub(crate) enum Deferred {
+    Focus(String),
+    Blur(String),
+    ScrollIntoView(String),
+    ScrollTo(f64, f64),
+}
+
+impl Deferred {
+    pub(crate) fn apply(&self, env: &Env) -> crate::Result<()> {
+        match self {
+            Deferred::Focus(id) => focus(env, id),
+            Deferred::Blur(id) => blur(env, id),
+            Deferred::ScrollIntoView(id) => {
+                if let Some(element) = env.get_element_by_id(id) {
+                    element.scroll_into_view();
+                }
+                Ok(())
+            }
+            Deferred::ScrollTo(x, y) => {
+                env.window.scroll_to_with_x_and_y(*x, *y);
+                Ok(())
+            }
+        }
+    }
+}
+
+pub(crate) fn focus(env: &Env, id: &str) -> crate::Result<()> {
+    if let Some(element) = env.get_element_by_id(id) {
+        if let Ok(element) = element.dyn_into::<web::HtmlElement>() {
+            element
+                .focus()
+                .map_err(crate::Error::dom("HTMLElement.focus"))?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn blur(env: &Env, id: &str) -> crate::Result<()> {
+    if let Some(element) = env.get_element_by_id(id) {
+        if let Ok(element) = element.dyn_into::<web::HtmlElement>() {
+            element
+                .blur()
+                .map_err(crate::Error::dom("HTMLElement.blur"))?;
+        }
+    }
+    Ok(())
+}
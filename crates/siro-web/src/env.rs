@@ -1,36 +1,117 @@
-use crate::app::App;
 use crate::subscription::Subscription;
+use crate::{
+    app::App,
+    error::ErrorKind,
+    storage::{Storage, StorageArea},
+    stylesheet::StyleSheet,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use siro::router::{Route, Router};
+use std::borrow::Cow;
+use wasm_bindgen::{JsCast, JsValue};
 
+#[derive(Clone)]
 pub struct Env {
     pub(crate) window: web::Window,
     pub(crate) document: web::Document,
-    pub(crate) local_storage: Option<web::Storage>,
+    /// The document or shadow root in which the element ids are resolved.
+    root: web::Node,
 }
 
 impl Env {
     pub fn new() -> crate::Result<Self> {
         let window =
-            web::window().ok_or_else(|| crate::Error::custom("no global Window exists"))?;
+            web::window().ok_or_else(|| crate::Error::new(ErrorKind::Unsupported("Window")))?;
 
         let document = window
             .document()
-            .ok_or_else(|| crate::Error::custom("no Document exists"))?;
-
-        let local_storage = window
-            .local_storage()
-            .map_err(crate::Error::caught_from_js)?;
+            .ok_or_else(|| crate::Error::new(ErrorKind::Unsupported("Document")))?;
 
         Ok(Self {
             window,
+            root: document.clone().into(),
             document,
-            local_storage,
         })
     }
 
+    /// Return the environment that resolves the element ids in the tree
+    /// containing the node, which may be in a shadow root.
+    pub(crate) fn scoped(&self, node: &web::Node) -> Self {
+        Self {
+            root: node.get_root_node(),
+            ..self.clone()
+        }
+    }
+
+    /// Find the element with the id in the document or the shadow root.
+    pub(crate) fn get_element_by_id(&self, id: &str) -> Option<web::Element> {
+        match self.root.dyn_ref::<web::ShadowRoot>() {
+            Some(shadow_root) => shadow_root.get_element_by_id(id),
+            None => self.document.get_element_by_id(id),
+        }
+    }
+
+    pub fn current_url(&self) -> Option<String> {
+        self.window.location().href().ok()
+    }
+
+    /// Parse the current URL into the route.
+    pub fn current_route<R>(&self, router: &Router) -> Option<R>
+    where
+        R: Route,
+    {
+        router.parse(&self.current_url()?)
+    }
+
     pub fn current_url_hash(&self) -> Option<String> {
         self.window.location().hash().ok()
     }
 
+    /// Return the size of the viewport, including the scrollbars.
+    pub fn window_size(&self) -> Size {
+        let dimension = |value: Result<JsValue, JsValue>| {
+            value.ok().and_then(|value| value.as_f64()).unwrap_or(0.0)
+        };
+        Size {
+            width: dimension(self.window.inner_width()),
+            height: dimension(self.window.inner_height()),
+        }
+    }
+
+    /// Return the visibility of the document.
+    pub fn visibility(&self) -> Visibility {
+        if self.document.hidden() {
+            Visibility::Hidden
+        } else {
+            Visibility::Visible
+        }
+    }
+
+    /// Return whether the browser is online.
+    pub fn is_online(&self) -> bool {
+        self.window.navigator().on_line()
+    }
+
+    /// Return whether the document matches the media query.
+    pub fn matches_media(&self, query: &str) -> crate::Result<bool> {
+        Ok(self.match_media(query)?.matches())
+    }
+
+    /// Return the color scheme preferred by the user.
+    pub fn prefers_color_scheme(&self) -> ColorScheme {
+        match self.matches_media(ColorScheme::DARK_QUERY) {
+            Ok(true) => ColorScheme::Dark,
+            _ => ColorScheme::Light,
+        }
+    }
+
+    pub(crate) fn match_media(&self, query: &str) -> crate::Result<web::MediaQueryList> {
+        self.window
+            .match_media(query)
+            .map_err(crate::Error::dom("Window.matchMedia"))?
+            .ok_or_else(|| crate::Error::new(ErrorKind::Unsupported("Window.matchMedia")))
+    }
+
     pub fn mount<TMsg>(&self, selector: &str) -> crate::Result<App<TMsg>>
     where
         TMsg: 'static,
@@ -38,9 +119,11 @@ impl Env {
         let node = self
             .document
             .query_selector(selector)
-            .map_err(crate::Error::caught_from_js)?
-            .ok_or_else(|| crate::Error::custom("missing node"))?;
-        Ok(App::new(self, node.into()))
+            .map_err(crate::Error::dom("Document.querySelector"))?
+            .ok_or_else(|| {
+                crate::Error::new(ErrorKind::MissingMountpoint(selector.to_owned().into()))
+            })?;
+        App::new(self, node.into())
     }
 
     pub fn mount_to_body<TMsg>(&self) -> crate::Result<App<TMsg>>
@@ -50,8 +133,68 @@ impl Env {
         let body = self
             .document
             .body()
-            .ok_or_else(|| crate::Error::custom("missing body in document"))?;
-        Ok(App::new(self, body.into()))
+            .ok_or_else(|| crate::Error::new(ErrorKind::MissingMountpoint("body".into())))?;
+        App::new(self, body.into())
+    }
+
+    /// Attach a shadow root to the element specified by the selector, and
+    /// mount an application on it.
+    ///
+    /// If the element already has a shadow root of the same mode, which was
+    /// attached by this method if it is closed, the shadow root is reused.
+    /// Otherwise, `ErrorKind::ShadowModeMismatch` is returned if the element
+    /// has a shadow root of the other mode.  The styles in the host page do not apply to the application, except
+    /// the inherited ones, and vice versa.
+    pub fn mount_shadow<TMsg>(
+        &self,
+        selector: &str,
+        mode: ShadowMode,
+    ) -> crate::Result<App<'_, TMsg>>
+    where
+        TMsg: 'static,
+    {
+        let host = self
+            .document
+            .query_selector(selector)
+            .map_err(crate::Error::dom("Document.querySelector"))?
+            .ok_or_else(|| {
+                crate::Error::new(ErrorKind::MissingMountpoint(selector.to_owned().into()))
+            })?;
+
+        // The closed shadow roots cannot be retrieved from the host, so the
+        // ones attached here are remembered for reuse.
+        thread_local! {
+            static CLOSED_SHADOW_ROOTS: js_sys::WeakMap = js_sys::WeakMap::new();
+        }
+        let closed_shadow_root =
+            CLOSED_SHADOW_ROOTS.with(|roots| roots.get(&host).dyn_into::<web::ShadowRoot>().ok());
+
+        let shadow_root = match (mode, host.shadow_root(), closed_shadow_root) {
+            (ShadowMode::Open, Some(shadow_root), _) => shadow_root,
+            (ShadowMode::Closed, _, Some(shadow_root)) => shadow_root,
+            (_, Some(..), _) | (_, _, Some(..)) => {
+                return Err(crate::Error::new(ErrorKind::ShadowModeMismatch(mode)));
+            }
+            (ShadowMode::Open, None, None) => host
+                .attach_shadow(&web::ShadowRootInit::new(web::ShadowRootMode::Open))
+                .map_err(crate::Error::dom("Element.attachShadow"))?,
+            (ShadowMode::Closed, None, None) => {
+                let shadow_root = host
+                    .attach_shadow(&web::ShadowRootInit::new(web::ShadowRootMode::Closed))
+                    .map_err(crate::Error::dom("Element.attachShadow"))?;
+                CLOSED_SHADOW_ROOTS.with(|roots| roots.set(&host, &shadow_root));
+                shadow_root
+            }
+        };
+
+        let mut app = App::new(self, shadow_root.into())?;
+        app.set_host(host.into());
+        Ok(app)
+    }
+
+    /// Create a constructable stylesheet from the CSS text.
+    pub fn create_stylesheet(&self, css: &str) -> crate::Result<StyleSheet> {
+        StyleSheet::new(css)
     }
 
     pub fn subscribe<S>(&self, subscription: S) -> crate::Result<S::Stream>
@@ -61,19 +204,83 @@ impl Env {
         subscription.subscribe(self)
     }
 
+    /// Create a handle to `localStorage` with the specified namespace.
+    pub fn local_storage<T>(
+        &self,
+        namespace: impl Into<Cow<'static, str>>,
+    ) -> crate::Result<Storage<T>>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        Storage::new(self, StorageArea::Local, namespace.into())
+    }
+
+    /// Create a handle to `sessionStorage` with the specified namespace.
+    pub fn session_storage<T>(
+        &self,
+        namespace: impl Into<Cow<'static, str>>,
+    ) -> crate::Result<Storage<T>>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        Storage::new(self, StorageArea::Session, namespace.into())
+    }
+
+    /// Return the raw string in `localStorage` associated with the key.
+    ///
+    /// An error of `ErrorKind::StorageUnavailable` is returned if the storage is not available.
     pub fn get_storage_item(&self, key: &str) -> crate::Result<Option<String>> {
-        match self.local_storage {
-            Some(ref storage) => storage.get_item(key).map_err(crate::Error::caught_from_js),
-            None => Ok(None),
-        }
+        StorageArea::Local
+            .storage(&self.window)?
+            .get_item(key)
+            .map_err(crate::Error::dom("Storage.getItem"))
     }
 
     pub fn set_storage_item(&self, key: &str, value: String) -> crate::Result<()> {
-        match self.local_storage {
-            Some(ref storage) => storage
-                .set_item(key, &*value)
-                .map_err(crate::Error::caught_from_js),
-            None => Ok(()),
-        }
+        StorageArea::Local
+            .storage(&self.window)?
+            .set_item(key, &value)
+            .map_err(crate::Error::dom("Storage.setItem"))
+    }
+
+    pub fn remove_storage_item(&self, key: &str) -> crate::Result<()> {
+        StorageArea::Local
+            .storage(&self.window)?
+            .remove_item(key)
+            .map_err(crate::Error::dom("Storage.removeItem"))
     }
 }
+
+/// The encapsulation mode of shadow roots.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ShadowMode {
+    /// The shadow root is accessible from the page via `element.shadowRoot`.
+    Open,
+    /// The shadow root is hidden from the page.
+    Closed,
+}
+
+/// The size of the viewport.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Size {
+    pub width: f64,
+    pub height: f64,
+}
+
+/// The visibility of the document.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Visibility {
+    Visible,
+    Hidden,
+}
+
+/// The color scheme preferred by the user.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ColorScheme {
+    Light,
+    Dark,
+}
+
+impl ColorScheme {
+    pub(crate) const DARK_QUERY: &'static str = "(prefers-color-scheme: dark)";
+}
@@ -1,38 +1,221 @@
-use std::borrow::Cow;
-use wasm_bindgen::JsValue;
+use crate::env::ShadowMode;
+use std::{borrow::Cow, fmt};
+use wasm_bindgen::{JsCast as _, JsValue};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
-#[derive(Debug)]
+type Source = Box<dyn std::error::Error + 'static>;
+
+/// The error type returned from the runtime.
+///
+/// The exceptions thrown from JavaScript are preserved as the source of
+/// errors, and can be retrieved by `Error::js_value`.
 pub struct Error {
     kind: ErrorKind,
+    source: Option<Source>,
 }
 
-#[derive(Debug)]
-enum ErrorKind {
-    CaughtFromJS(JsValue),
+/// The kind of `Error`.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The node to mount the application, specified by the selector, is missing.
+    MissingMountpoint(Cow<'static, str>),
+    /// Another application is already mounted on the node.
+    AlreadyMounted,
+    /// The host already has a shadow root of the other mode than the given one.
+    ShadowModeMismatch(ShadowMode),
+    /// The element specified by the id or the selector is missing.
+    MissingElement(Cow<'static, str>),
+    /// The DOM operation with the given name has failed.
+    Dom(&'static str),
+    /// Failed to serialize the value of the property with the given name.
+    SerializeProperty(Cow<'static, str>),
+    /// Failed to decode the event with the given type.
+    DecodeEvent(Cow<'static, str>),
+    /// The drawing is attached to the element with the given tag name,
+    /// which is not `<canvas>`.
+    NotCanvas(Cow<'static, str>),
+    /// The storage is not available in this environment.
+    StorageUnavailable,
+    /// The storage quota has been exceeded.
+    QuotaExceeded,
+    /// The Web API with the given name is not available in this environment.
+    Unsupported(&'static str),
+    /// An error with an arbitrary message.
     Custom(Cow<'static, str>),
 }
 
 impl Error {
+    /// Create an error of the specified kind.
+    pub fn new(kind: ErrorKind) -> Self {
+        Self { kind, source: None }
+    }
+
+    /// Create an error with an arbitrary message.
+    pub fn custom(msg: impl Into<Cow<'static, str>>) -> Self {
+        Self::new(ErrorKind::Custom(msg.into()))
+    }
+
+    /// Create an error caused by the exception thrown from JavaScript.
+    #[deprecated(note = "use `Error::new(..).with_js_value(..)` instead")]
     pub fn caught_from_js(payload: JsValue) -> Self {
-        Self {
-            kind: ErrorKind::CaughtFromJS(payload),
+        Self::custom("an exception was thrown from JavaScript").with_js_value(payload)
+    }
+
+    /// Attach the underlying cause of this error.
+    pub fn with_source(mut self, source: impl Into<Source>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Attach the exception thrown from JavaScript as the cause of this error.
+    pub fn with_js_value(self, value: JsValue) -> Self {
+        self.with_source(JsException::new(value))
+    }
+
+    /// Return a function that converts the exception thrown from the DOM
+    /// operation into an error.
+    pub(crate) fn dom(operation: &'static str) -> impl FnOnce(JsValue) -> Self {
+        move |value| {
+            let kind = match JsException::name(&value).as_deref() {
+                Some("QuotaExceededError") => ErrorKind::QuotaExceeded,
+                _ => ErrorKind::Dom(operation),
+            };
+            Self::new(kind).with_js_value(value)
         }
     }
 
-    pub fn custom(msg: impl Into<Cow<'static, str>>) -> Self {
-        Self {
-            kind: ErrorKind::Custom(msg.into()),
+    /// Return the kind of this error.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    /// Return the exception thrown from JavaScript, if this error is caused by it.
+    pub fn js_value(&self) -> Option<&JsValue> {
+        let mut source = self.source.as_deref();
+        while let Some(err) = source {
+            if let Some(exception) = err.downcast_ref::<JsException>() {
+                return Some(&exception.value);
+            }
+            source = err.source();
+        }
+        None
+    }
+}
+
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Error")
+            .field("kind", &self.kind)
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ErrorKind::MissingMountpoint(ref selector) => {
+                write!(f, "the mount point `{}` is missing", selector)
+            }
+            ErrorKind::AlreadyMounted => {
+                f.write_str("another application is already mounted on the node")
+            }
+            ErrorKind::ShadowModeMismatch(mode) => write!(
+                f,
+                "the host already has a shadow root of the other mode than {:?}",
+                mode
+            ),
+            ErrorKind::MissingElement(ref target) => {
+                write!(f, "the element `{}` is missing", target)
+            }
+            ErrorKind::Dom(operation) => write!(f, "the DOM operation `{}` failed", operation),
+            ErrorKind::SerializeProperty(ref name) => {
+                write!(f, "failed to serialize the property `{}`", name)
+            }
+            ErrorKind::DecodeEvent(ref event_type) => {
+                write!(f, "failed to decode the `{}` event", event_type)
+            }
+            ErrorKind::NotCanvas(ref tag_name) => write!(
+                f,
+                "the drawing is attached to `<{}>`, which is not a canvas",
+                tag_name
+            ),
+            ErrorKind::StorageUnavailable => f.write_str("the storage is not available"),
+            ErrorKind::QuotaExceeded => f.write_str("the storage quota has been exceeded"),
+            ErrorKind::Unsupported(api) => {
+                write!(f, "`{}` is not available in this environment", api)
+            }
+            ErrorKind::Custom(ref msg) => f.write_str(msg),
         }
     }
 }
 
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_deref()
+    }
+}
+
+/// The error is converted into a JavaScript `Error` whose message includes
+/// the chain of sources, and whose `cause` is the original exception.
 impl From<Error> for JsValue {
     fn from(error: Error) -> Self {
-        match error.kind {
-            ErrorKind::CaughtFromJS(payload) => payload,
-            ErrorKind::Custom(msg) => JsValue::from_str(&*msg),
+        let mut message = error.to_string();
+        let mut source = std::error::Error::source(&error);
+        while let Some(err) = source {
+            message += ": ";
+            message += &err.to_string();
+            source = err.source();
+        }
+
+        let js_error = js_sys::Error::new(&message);
+        if let Some(cause) = error.js_value() {
+            let _ = js_sys::Reflect::set(&js_error, &JsValue::from_str("cause"), cause);
         }
+        js_error.into()
     }
 }
+
+/// An exception thrown from JavaScript.
+struct JsException {
+    value: JsValue,
+}
+
+impl JsException {
+    fn new(value: JsValue) -> Self {
+        Self { value }
+    }
+
+    fn name(value: &JsValue) -> Option<String> {
+        value
+            .dyn_ref::<js_sys::Error>()
+            .map(|err| String::from(err.name()))
+    }
+}
+
+impl fmt::Debug for JsException {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.value, f)
+    }
+}
+
+impl fmt::Display for JsException {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(err) = self.value.dyn_ref::<js_sys::Error>() {
+            write!(
+                f,
+                "{}: {}",
+                String::from(err.name()),
+                String::from(err.message())
+            )
+        } else if let Some(s) = self.value.as_string() {
+            f.write_str(&s)
+        } else {
+            write!(f, "{:?}", self.value)
+        }
+    }
+}
+
+impl std::error::Error for JsException {}
@@ -3,17 +3,33 @@ A browser runtime for `siro` using `web-sys`.
 */
 
 #![doc(html_root_url = "https://docs.rs/siro-web/0.1.0")]
-#![forbid(unsafe_code, clippy::todo, clippy::unimplemented)]
+#![forbid(unsafe_code)]
+// Not forbidden, since the bindings generated by `#[wasm_bindgen]` allow them.
+#![deny(clippy::todo, clippy::unimplemented)]
 
 mod app;
+mod canvas;
+mod custom_element;
+mod effects;
 mod env;
 mod error;
+mod link;
+mod program;
 mod render;
+mod storage;
+mod stylesheet;
 
+pub mod debug;
 pub mod subscription;
 
 pub use crate::{
-    app::App,
-    env::Env,
-    error::{Error, Result},
+    app::{App, DropBehavior, Step},
+    custom_element::CustomElement,
+    effects::Effects,
+    env::{ColorScheme, Env, ShadowMode, Size, Visibility},
+    error::{Error, ErrorKind, Result},
+    link::link,
+    program::{run, run_app},
+    storage::{Storage, StorageArea},
+    stylesheet::StyleSheet,
 };
@@ -0,0 +1,187 @@
+use gloo_events::{EventListener, EventListenerOptions};
+use serde::Deserialize;
+use siro::{
+    html::{self, attr},
+    router::{Route, Router},
+    vdom::{Attributes, AttributesRenderer, Event, EventDecoder, Hook, HookKind, Nodes},
+};
+use std::{cell::RefCell, marker::PhantomData, rc::Rc};
+use wasm_bindgen::JsCast;
+
+/// Create a `View` of `<a>` element that navigates to the route without
+/// reloading the page.
+///
+/// The left clicks on the link are intercepted, and the URL is changed by
+/// `history.pushState` before the message returned from `f` is emitted.
+/// The clicks with modifier keys or other buttons, and the links to other
+/// origins or with `target` attribute, are handled by the browser as usual.
+pub fn link<R, TMsg>(
+    router: Router,
+    route: R,
+    f: impl Fn(R) -> TMsg + 'static,
+    attributes: impl Attributes<TMsg>,
+    children: impl Nodes<TMsg>,
+) -> impl Nodes<TMsg>
+where
+    R: Route + Clone + 'static,
+    TMsg: 'static,
+{
+    let url = router.url_for(&route);
+    html::a(
+        (
+            attr::href(url.clone()),
+            attributes,
+            OnLinkClick {
+                url,
+                route,
+                f,
+                _marker: PhantomData,
+            },
+        ),
+        children,
+    )
+}
+
+struct OnLinkClick<R, F, TMsg> {
+    url: String,
+    route: R,
+    f: F,
+    _marker: PhantomData<fn() -> TMsg>,
+}
+
+impl<R, F, TMsg> Attributes<TMsg> for OnLinkClick<R, F, TMsg>
+where
+    R: Clone + 'static,
+    F: Fn(R) -> TMsg + 'static,
+    TMsg: 'static,
+{
+    fn render_attributes<T>(self, mut renderer: T) -> Result<T::Ok, T::Error>
+    where
+        T: AttributesRenderer<Msg = TMsg>,
+    {
+        // The navigation is done by a listener on the raw event, since the
+        // decoder cannot cancel the default action.  It is registered by
+        // the hooks after the rendering, so that it runs after the decoder
+        // that sees whether the event has been canceled by others.
+        let interceptor = Interceptor {
+            url: self.url.clone(),
+            listener: Rc::default(),
+        };
+        renderer.hook(Hook::new(HookKind::Mount, interceptor.clone().into_fn()))?;
+        renderer.hook(Hook::new(HookKind::Update, interceptor.into_fn()))?;
+        renderer.event("click", self)?;
+        renderer.end()
+    }
+}
+
+/// The listener that cancels the intercepted clicks and changes the URL.
+///
+/// The listener is removed when the element is re-rendered or removed,
+/// since the hooks holding it are dropped.
+#[derive(Clone)]
+struct Interceptor {
+    url: String,
+    listener: Rc<RefCell<Option<EventListener>>>,
+}
+
+impl Interceptor {
+    fn into_fn(self) -> impl Fn(&web::Element) + Clone {
+        move |element| self.listen(element)
+    }
+
+    fn listen(&self, element: &web::Element) {
+        let anchor = match element.dyn_ref::<web::HtmlAnchorElement>() {
+            Some(anchor) => anchor.clone(),
+            None => return,
+        };
+        let url = self.url.clone();
+        let listener = EventListener::new_with_options(
+            element,
+            "click",
+            EventListenerOptions::enable_prevent_default(),
+            move |event| {
+                let event = match event.dyn_ref::<web::MouseEvent>() {
+                    Some(event) => event,
+                    None => return,
+                };
+                if !ClickEvent::from_raw(event, &anchor).is_intercepted() {
+                    return;
+                }
+                // Fall back to the default navigation if the history is not available.
+                if let Some(window) = web::window() {
+                    if crate::effects::push_url(&window, &url).is_ok() {
+                        event.prevent_default();
+                    }
+                }
+            },
+        );
+        self.listener.replace(Some(listener));
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ClickEvent {
+    button: i16,
+    ctrl_key: bool,
+    meta_key: bool,
+    shift_key: bool,
+    alt_key: bool,
+    default_prevented: bool,
+    current_target: ClickTarget,
+}
+
+#[derive(Deserialize)]
+struct ClickTarget {
+    origin: String,
+    target: String,
+}
+
+impl ClickEvent {
+    fn from_raw(event: &web::MouseEvent, anchor: &web::HtmlAnchorElement) -> Self {
+        Self {
+            button: event.button(),
+            ctrl_key: event.ctrl_key(),
+            meta_key: event.meta_key(),
+            shift_key: event.shift_key(),
+            alt_key: event.alt_key(),
+            default_prevented: event.default_prevented(),
+            current_target: ClickTarget {
+                origin: anchor.origin(),
+                target: anchor.target(),
+            },
+        }
+    }
+
+    fn is_intercepted(&self) -> bool {
+        let is_same_origin = web::window()
+            .and_then(|window| window.location().origin().ok())
+            .is_some_and(|origin| origin == self.current_target.origin);
+
+        self.button == 0
+            && !(self.ctrl_key || self.meta_key || self.shift_key || self.alt_key)
+            && !self.default_prevented
+            && self.current_target.target.is_empty()
+            && is_same_origin
+    }
+}
+
+impl<R, F, TMsg> EventDecoder for OnLinkClick<R, F, TMsg>
+where
+    R: Clone,
+    F: Fn(R) -> TMsg,
+    TMsg: 'static,
+{
+    type Msg = TMsg;
+
+    fn decode_event<E>(&self, event: E) -> Result<Option<Self::Msg>, E::Error>
+    where
+        E: Event,
+    {
+        let click: ClickEvent = event.decode()?;
+        if !click.is_intercepted() {
+            return Ok(None);
+        }
+        Ok(Some((self.f)(self.route.clone())))
+    }
+}
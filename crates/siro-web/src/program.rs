@@ -0,0 +1,166 @@
+use crate::{
+    app::{App, Step},
+    effects::Effects,
+    env::Env,
+    subscription::Subscription,
+};
+use futures::{prelude::*, select};
+use siro::{
+    vdom::{Nodes, NodesRenderer},
+    Cmd, Program,
+};
+
+/// Run a `Program` on the DOM node specified by the selector.
+///
+/// The returned future drives the event loop of the program.  After applying
+/// all messages that are ready at once, the subscriptions are refreshed if
+/// they differ from the previous ones, and the program is re-rendered on the
+/// next animation frame.
+pub async fn run<P>(program: P, selector: &str) -> crate::Result<()>
+where
+    P: Program,
+    P::Subscriptions: Subscription<Msg = P::Msg> + Clone + PartialEq,
+{
+    let env = Env::new()?;
+    let app = env.mount(selector)?;
+    run_app(program, app).await
+}
+
+/// Run a `Program` on the already mounted `App`.
+///
+/// This is the same as `run`, except that the application is mounted by
+/// the caller, e.g. into the shadow root of a custom element.
+pub async fn run_app<P>(program: P, app: App<'_, P::Msg>) -> crate::Result<()>
+where
+    P: Program,
+    P::Subscriptions: Subscription<Msg = P::Msg> + Clone + PartialEq,
+{
+    let model = program.init();
+    drive(
+        ProgramRuntime {
+            program: &program,
+            model,
+        },
+        app,
+    )
+    .await
+}
+
+/// The state of a program driven by `drive`.
+///
+/// This abstracts over the plain runtime and the debug one, which records
+/// the messages passed to `update` and renders its panel in `render`.
+pub(crate) trait Runtime {
+    type Msg: 'static;
+    type Subscriptions: Subscription<Msg = Self::Msg> + Clone + PartialEq;
+
+    fn update(&mut self, msg: Self::Msg, effects: &mut Effects<'_, Self::Msg>) -> Cmd<Self::Msg>;
+
+    fn render(&self, app: &mut App<'_, Self::Msg>) -> crate::Result<()>;
+
+    fn subscriptions(&self) -> Self::Subscriptions;
+}
+
+/// Drive the event loop of the runtime on the `App`.
+///
+/// After applying all messages that are ready at once, the subscriptions are
+/// refreshed if they differ from the previous ones, and the view is
+/// re-rendered on the next animation frame.
+pub(crate) async fn drive<R>(mut runtime: R, mut app: App<'_, R::Msg>) -> crate::Result<()>
+where
+    R: Runtime,
+{
+    runtime.render(&mut app)?;
+
+    let mut subscriptions = runtime.subscriptions();
+    let mut stream = Box::pin(app.subscribe(subscriptions.clone())?);
+
+    loop {
+        let step = select! {
+            step = app.next_step().fuse() => step,
+            msg = stream.select_next_some() => Some(Step::Message(msg)),
+            complete => break,
+        };
+        let msg = match step {
+            Some(Step::Message(msg)) => msg,
+            Some(Step::Render) => {
+                runtime.render(&mut app)?;
+                continue;
+            }
+            None => break,
+        };
+        let cmd = runtime.update(msg, &mut app.effects());
+        app.spawn(cmd);
+
+        // Apply the remaining messages that have already arrived before rendering.
+        loop {
+            if let Some(msg) = app.try_next_message() {
+                let cmd = runtime.update(msg, &mut app.effects());
+                app.spawn(cmd);
+                continue;
+            }
+            if let Some(Some(msg)) = stream.next().now_or_never() {
+                let cmd = runtime.update(msg, &mut app.effects());
+                app.spawn(cmd);
+                continue;
+            }
+            break;
+        }
+
+        let new_subscriptions = runtime.subscriptions();
+        if new_subscriptions != subscriptions {
+            // The old stream is dropped here and its resources are released.
+            stream = Box::pin(app.subscribe(new_subscriptions.clone())?);
+            subscriptions = new_subscriptions;
+        }
+
+        // The view is rendered once on the next animation frame, however many
+        // messages arrive until then.
+        app.request_render()?;
+    }
+
+    Ok(())
+}
+
+struct ProgramRuntime<'a, P: Program> {
+    program: &'a P,
+    model: P::Model,
+}
+
+impl<P> Runtime for ProgramRuntime<'_, P>
+where
+    P: Program,
+    P::Subscriptions: Subscription<Msg = P::Msg> + Clone + PartialEq,
+{
+    type Msg = P::Msg;
+    type Subscriptions = P::Subscriptions;
+
+    fn update(&mut self, msg: Self::Msg, effects: &mut Effects<'_, Self::Msg>) -> Cmd<Self::Msg> {
+        self.program.update(&mut self.model, msg, effects)
+    }
+
+    fn render(&self, app: &mut App<'_, Self::Msg>) -> crate::Result<()> {
+        app.render(View {
+            program: self.program,
+            model: &self.model,
+        })
+    }
+
+    fn subscriptions(&self) -> Self::Subscriptions {
+        self.program.subscriptions(&self.model)
+    }
+}
+
+pub(crate) struct View<'a, P: Program> {
+    pub(crate) program: &'a P,
+    pub(crate) model: &'a P::Model,
+}
+
+impl<P: Program> Nodes<P::Msg> for View<'_, P> {
+    fn render_nodes<R>(self, renderer: R) -> Result<R::Ok, R::Error>
+    where
+        R: NodesRenderer<Msg = P::Msg>,
+    {
+        self.program.view(self.model, renderer)
+    }
+}
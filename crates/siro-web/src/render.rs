@@ -1,11 +1,15 @@
+use crate::{canvas::Canvas, error::ErrorKind};
 use futures::channel::mpsc;
 use gloo_events::EventListener;
 use serde::Serialize;
-use siro::vdom::{
-    AttributeValue, Attributes, AttributesRenderer, CowStr, Event, EventDecoder, Nodes,
-    NodesRenderer,
+use siro::{
+    canvas::Drawing,
+    vdom::{
+        AttributeValue, Attributes, AttributesRenderer, CowStr, Event, EventDecoder, Hook,
+        HookKind, NodeRef, Nodes, NodesRenderer,
+    },
 };
-use std::mem;
+use std::{cell::Cell, cell::RefCell, mem, rc::Rc};
 use wasm_bindgen::prelude::*;
 
 // ==== VNode ====
@@ -17,16 +21,50 @@ type FxIndexSet<T> = indexmap::IndexSet<T, BuildFxHasher>;
 #[derive(Debug)]
 pub(crate) enum VNode {
     Text(VText),
-    Element(VElement),
+    Element(Box<VElement>),
+    Portal(VPortal),
 }
 
 impl VNode {
-    fn as_node(&self) -> Option<&web::Node> {
+    fn as_node(&self) -> &web::Node {
         match self {
-            VNode::Text(VText { node, .. }) => Some(node.as_ref()),
-            VNode::Element(VElement { node, .. }) => Some(node.as_ref()),
+            VNode::Text(VText { node, .. }) => node.as_ref(),
+            VNode::Element(velement) => velement.node.as_ref(),
+            VNode::Portal(vportal) => vportal.anchor.as_ref(),
         }
     }
+
+    /// Remove the nodes from the document.
+    pub(crate) fn detach(&self, parent: &web::Node) -> crate::Result<()> {
+        self.release()?;
+        parent
+            .remove_child(self.as_node())
+            .map_err(crate::Error::dom("Node.removeChild"))?;
+        Ok(())
+    }
+
+    /// Call the unmount hooks and clear the node references in this subtree,
+    /// and remove the children of the portals in it from their containers.
+    fn release(&self) -> crate::Result<()> {
+        match self {
+            VNode::Text(..) => (),
+            VNode::Element(velement) => {
+                velement.call_hooks(HookKind::Unmount);
+                if let Some(ref node_ref) = velement.node_ref {
+                    node_ref.clear(&velement.node);
+                }
+                for child in &velement.children {
+                    child.release()?;
+                }
+            }
+            VNode::Portal(vportal) => {
+                for child in &vportal.children {
+                    child.detach(&vportal.container)?;
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -39,11 +77,28 @@ pub(crate) struct VElement {
     class_names: FxIndexSet<CowStr>,
     styles: FxIndexMap<CowStr, CowStr>,
     inner_html: Option<CowStr>,
+    hooks: Vec<Hook>,
+    node_ref: Option<NodeRef>,
+    canvas: Option<Canvas>,
     children: Vec<VNode>,
     node: web::Element,
 }
 
 impl VElement {
+    fn call_hooks(&self, kind: HookKind) {
+        for hook in self.hooks.iter().filter(|hook| hook.kind() == kind) {
+            hook.call(&self.node);
+        }
+    }
+
+    /// Queue the hooks of the kind, which are called after the rendering is completed.
+    fn schedule_hooks(&self, kind: HookKind, pending: &RefCell<Vec<PendingHook>>) {
+        let hooks = self.hooks.iter().filter(|hook| hook.kind() == kind);
+        pending
+            .borrow_mut()
+            .extend(hooks.map(|hook| (hook.clone(), self.node.clone())));
+    }
+
     fn apply_class(&self) -> crate::Result<()> {
         let class_name = self.class_names.iter().fold(String::new(), |mut acc, c| {
             if !acc.is_empty() {
@@ -80,14 +135,28 @@ pub(super) struct VText {
     node: web::Text,
 }
 
+#[derive(Debug)]
+pub(super) struct VPortal {
+    target: CowStr,
+    container: web::Node,
+    children: Vec<VNode>,
+    /// The empty comment that keeps the position of the portal in its parent.
+    anchor: web::Comment,
+}
+
 // ==== RenderContext ====
 
 pub(super) struct RenderContext<'ctx, TMsg> {
     pub(super) document: &'ctx web::Document,
     pub(super) parent: &'ctx web::Node,
     pub(super) tx: &'ctx mpsc::UnboundedSender<TMsg>,
+    pub(super) decode_errors: &'ctx Rc<DecodeErrors<TMsg>>,
+    pub(super) pending_hooks: &'ctx RefCell<Vec<PendingHook>>,
 }
 
+/// The hook to be called with the element after the rendering is completed.
+pub(super) type PendingHook = (Hook, web::Element);
+
 impl<TMsg: 'static> RenderContext<'_, TMsg> {
     pub(super) fn diff_nodes<N>(&self, nodes: N, vnodes: &mut Vec<VNode>) -> crate::Result<()>
     where
@@ -106,10 +175,37 @@ impl<TMsg: 'static> RenderContext<'_, TMsg> {
         RenderContext {
             document: &*self.document,
             tx: &*self.tx,
+            decode_errors: &*self.decode_errors,
+            pending_hooks: self.pending_hooks,
             parent,
         }
     }
 
+    fn event_listener<D>(
+        &self,
+        node: &web::Element,
+        event_type: &'static str,
+        decoder: D,
+    ) -> EventListener
+    where
+        D: EventDecoder<Msg = TMsg> + 'static,
+    {
+        let tx = self.tx.clone();
+        let decode_errors = self.decode_errors.clone();
+        EventListener::new(node, event_type, move |event| {
+            let msg = match decoder.decode_event(AppEvent { event }) {
+                Ok(msg) => msg,
+                Err(err) => decode_errors.handle(
+                    crate::Error::new(ErrorKind::DecodeEvent(event_type.into()))
+                        .with_js_value(err.into()),
+                ),
+            };
+            if let Some(msg) = msg {
+                let _ = tx.unbounded_send(msg);
+            }
+        })
+    }
+
     fn create_element<A, C>(
         &self,
         tag_name: CowStr,
@@ -125,11 +221,11 @@ impl<TMsg: 'static> RenderContext<'_, TMsg> {
             Some(uri) => self
                 .document
                 .create_element_ns(Some(&*uri), &*tag_name)
-                .map_err(crate::Error::caught_from_js)?,
+                .map_err(crate::Error::dom("Document.createElementNS"))?,
             None => self
                 .document
                 .create_element(&*tag_name)
-                .map_err(crate::Error::caught_from_js)?,
+                .map_err(crate::Error::dom("Document.createElement"))?,
         };
 
         let mut velement = VElement {
@@ -142,12 +238,16 @@ impl<TMsg: 'static> RenderContext<'_, TMsg> {
             class_names: FxIndexSet::default(),
             styles: FxIndexMap::default(),
             inner_html: None,
+            hooks: vec![],
+            node_ref: None,
+            canvas: None,
             children: vec![],
         };
 
         attrs.render_attributes(NewAttributes {
             ctx: self,
             velement: &mut velement,
+            drawing: None,
         })?;
 
         if velement.inner_html.is_none() {
@@ -157,7 +257,12 @@ impl<TMsg: 'static> RenderContext<'_, TMsg> {
 
         self.parent
             .append_child(&velement.node)
-            .map_err(crate::Error::caught_from_js)?;
+            .map_err(crate::Error::dom("Node.appendChild"))?;
+
+        if let Some(ref node_ref) = velement.node_ref {
+            node_ref.set(velement.node.clone());
+        }
+        velement.schedule_hooks(HookKind::Mount, self.pending_hooks);
 
         Ok(velement)
     }
@@ -166,7 +271,7 @@ impl<TMsg: 'static> RenderContext<'_, TMsg> {
         let node = self.document.create_text_node(&*data);
         self.parent
             .append_child(&node)
-            .map_err(crate::Error::caught_from_js)?;
+            .map_err(crate::Error::dom("Node.appendChild"))?;
         Ok(VText { node, data })
     }
 
@@ -189,6 +294,8 @@ impl<TMsg: 'static> RenderContext<'_, TMsg> {
                 let old_attributes = mem::take(&mut velement.attributes);
                 let old_properties = mem::take(&mut velement.properties);
                 let old_inner_html = velement.inner_html.take();
+                let old_node_ref = velement.node_ref.take();
+                velement.hooks.clear();
                 velement.listeners.clear();
                 velement.class_names.clear();
                 velement.styles.clear();
@@ -199,17 +306,32 @@ impl<TMsg: 'static> RenderContext<'_, TMsg> {
                     old_attributes,
                     old_properties,
                     old_inner_html,
+                    drawing: None,
+                    resized: false,
                 })?;
 
                 if velement.inner_html.is_none() {
                     self.reparent(&velement.node)
                         .diff_nodes(children, &mut velement.children)?;
                 }
+
+                match (old_node_ref, &velement.node_ref) {
+                    (Some(old), Some(new)) if old == *new => (),
+                    (old, new) => {
+                        if let Some(old) = old {
+                            old.clear(&velement.node);
+                        }
+                        if let Some(new) = new {
+                            new.set(velement.node.clone());
+                        }
+                    }
+                }
+                velement.schedule_hooks(HookKind::Update, self.pending_hooks);
             }
 
             _ => {
                 let velement = self.create_element(tag_name, namespace_uri, attrs, children)?;
-                *vnode = VNode::Element(velement);
+                self.replace(vnode, VNode::Element(Box::new(velement)))?;
             }
         }
 
@@ -226,12 +348,68 @@ impl<TMsg: 'static> RenderContext<'_, TMsg> {
             }
             _ => {
                 let vtext = self.create_text_node(data)?;
-                *vnode = VNode::Text(vtext);
+                self.replace(vnode, VNode::Text(vtext))?;
             }
         }
 
         Ok(())
     }
+
+    fn create_portal<C>(&self, target: CowStr, children: C) -> crate::Result<VPortal>
+    where
+        C: Nodes<TMsg>,
+    {
+        let container: web::Node = self
+            .document
+            .query_selector(&target)
+            .map_err(crate::Error::dom("Document.querySelector"))?
+            .ok_or_else(|| crate::Error::new(ErrorKind::MissingElement(target.clone())))?
+            .into();
+
+        let anchor = self.document.create_comment("");
+        self.parent
+            .append_child(&anchor)
+            .map_err(crate::Error::dom("Node.appendChild"))?;
+
+        let mut vportal = VPortal {
+            target,
+            container,
+            children: vec![],
+            anchor,
+        };
+        self.reparent(&vportal.container)
+            .diff_nodes(children, &mut vportal.children)?;
+
+        Ok(vportal)
+    }
+
+    fn diff_portal<C>(&self, vnode: &mut VNode, target: CowStr, children: C) -> crate::Result<()>
+    where
+        C: Nodes<TMsg>,
+    {
+        match vnode {
+            VNode::Portal(vportal) if vportal.target == target => {
+                self.reparent(&vportal.container)
+                    .diff_nodes(children, &mut vportal.children)?;
+            }
+            _ => {
+                let vportal = self.create_portal(target, children)?;
+                self.replace(vnode, VNode::Portal(vportal))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replace the old node with the newly created one, keeping its position.
+    fn replace(&self, vnode: &mut VNode, new_vnode: VNode) -> crate::Result<()> {
+        vnode.release()?;
+        self.parent
+            .replace_child(new_vnode.as_node(), vnode.as_node())
+            .map_err(crate::Error::dom("Node.replaceChild"))?;
+        *vnode = new_vnode;
+        Ok(())
+    }
 }
 
 struct DiffNodes<'a, 'ctx, TMsg> {
@@ -263,7 +441,7 @@ impl<TMsg: 'static> NodesRenderer for DiffNodes<'_, '_, TMsg> {
             let vnode = self
                 .ctx
                 .create_element(tag_name, namespace_uri, attrs, children)
-                .map(VNode::Element)?;
+                .map(|velement| VNode::Element(Box::new(velement)))?;
             self.vnodes.push(vnode);
         }
         self.num_children += 1;
@@ -281,14 +459,26 @@ impl<TMsg: 'static> NodesRenderer for DiffNodes<'_, '_, TMsg> {
         Ok(())
     }
 
+    fn portal<C>(&mut self, target: CowStr, children: C) -> Result<(), Self::Error>
+    where
+        C: Nodes<Self::Msg>,
+    {
+        if let Some(vnode) = self.vnodes.get_mut(self.num_children) {
+            self.ctx.diff_portal(vnode, target, children)?;
+        } else {
+            let vnode = self
+                .ctx
+                .create_portal(target, children)
+                .map(VNode::Portal)?;
+            self.vnodes.push(vnode);
+        }
+        self.num_children += 1;
+        Ok(())
+    }
+
     fn end(self) -> Result<Self::Ok, Self::Error> {
         for vnode in self.vnodes.drain(self.num_children..) {
-            if let Some(node) = vnode.as_node() {
-                self.ctx
-                    .parent
-                    .remove_child(node)
-                    .map_err(crate::Error::caught_from_js)?;
-            }
+            vnode.detach(self.ctx.parent)?;
         }
 
         Ok(())
@@ -301,6 +491,8 @@ struct DiffAttributes<'a, 'ctx, TMsg> {
     old_attributes: FxIndexMap<CowStr, AttributeValue>,
     old_properties: FxIndexMap<CowStr, JsValue>,
     old_inner_html: Option<CowStr>,
+    drawing: Option<Drawing>,
+    resized: bool,
 }
 
 impl<TMsg: 'static> AttributesRenderer for DiffAttributes<'_, '_, TMsg> {
@@ -311,7 +503,10 @@ impl<TMsg: 'static> AttributesRenderer for DiffAttributes<'_, '_, TMsg> {
     fn attribute(&mut self, name: CowStr, value: AttributeValue) -> Result<(), Self::Error> {
         match self.old_attributes.remove(&*name) {
             Some(old_value) if old_value == value => (),
-            _ => set_attribute(&self.velement.node, &*name, &value)?,
+            _ => {
+                set_attribute(&self.velement.node, &*name, &value)?;
+                self.resized |= is_canvas_size(&name);
+            }
         }
         self.velement.attributes.insert(name, value);
         Ok(())
@@ -321,8 +516,9 @@ impl<TMsg: 'static> AttributesRenderer for DiffAttributes<'_, '_, TMsg> {
     where
         T: Serialize,
     {
-        let js_value = serde_wasm_bindgen::to_value(&value)
-            .map_err(|err| crate::Error::caught_from_js(err.into()))?;
+        let js_value = serde_wasm_bindgen::to_value(&value).map_err(|err| {
+            crate::Error::new(ErrorKind::SerializeProperty(name.clone())).with_js_value(err.into())
+        })?;
 
         match self.old_properties.remove(&*name) {
             Some(old_value) if old_value == js_value => (),
@@ -336,15 +532,9 @@ impl<TMsg: 'static> AttributesRenderer for DiffAttributes<'_, '_, TMsg> {
     where
         D: EventDecoder<Msg = Self::Msg> + 'static,
     {
-        let tx = self.ctx.tx.clone();
-        let listener = EventListener::new(&self.velement.node, event_type, move |event| {
-            if let Some(msg) = decoder
-                .decode_event(AppEvent { event })
-                .expect_throw("failed to decode Event")
-            {
-                tx.unbounded_send(msg).unwrap_throw();
-            }
-        });
+        let listener = self
+            .ctx
+            .event_listener(&self.velement.node, event_type, decoder);
         self.velement.listeners.insert(event_type.into(), listener);
         Ok(())
     }
@@ -371,12 +561,28 @@ impl<TMsg: 'static> AttributesRenderer for DiffAttributes<'_, '_, TMsg> {
         Ok(())
     }
 
-    fn end(self) -> Result<Self::Ok, Self::Error> {
+    fn drawing(&mut self, drawing: Drawing) -> Result<(), Self::Error> {
+        self.drawing.replace(drawing);
+        Ok(())
+    }
+
+    fn hook(&mut self, hook: Hook) -> Result<(), Self::Error> {
+        self.velement.hooks.push(hook);
+        Ok(())
+    }
+
+    fn node_ref(&mut self, node_ref: NodeRef) -> Result<(), Self::Error> {
+        self.velement.node_ref.replace(node_ref);
+        Ok(())
+    }
+
+    fn end(mut self) -> Result<Self::Ok, Self::Error> {
         for (name, _) in self.old_attributes {
+            self.resized |= is_canvas_size(&name);
             self.velement
                 .node
                 .remove_attribute(&*name)
-                .map_err(crate::Error::caught_from_js)?;
+                .map_err(crate::Error::dom("Element.removeAttribute"))?;
         }
 
         for (name, _) in self.old_properties {
@@ -386,15 +592,35 @@ impl<TMsg: 'static> AttributesRenderer for DiffAttributes<'_, '_, TMsg> {
         self.velement.apply_class()?;
         self.velement.apply_style()?;
 
+        match (&self.velement.canvas, self.drawing) {
+            (Some(canvas), Some(drawing)) => canvas.update(drawing, self.resized)?,
+            (None, Some(drawing)) => {
+                let canvas = Canvas::new(&self.velement.node, drawing)?;
+                self.velement.canvas.replace(canvas);
+            }
+            (Some(canvas), None) => {
+                canvas.clear();
+                self.velement.canvas.take();
+            }
+            (None, None) => (),
+        }
+
         Ok(())
     }
 }
 
+/// Return whether the attribute determines the size of `<canvas>`,
+/// whose change clears the drawn content.
+fn is_canvas_size(name: &str) -> bool {
+    name == "width" || name == "height"
+}
+
 // ==== NewAttributes ====
 
 struct NewAttributes<'a, 'ctx, TMsg> {
     ctx: &'a RenderContext<'ctx, TMsg>,
     velement: &'a mut VElement,
+    drawing: Option<Drawing>,
 }
 
 impl<TMsg: 'static> AttributesRenderer for NewAttributes<'_, '_, TMsg> {
@@ -412,8 +638,9 @@ impl<TMsg: 'static> AttributesRenderer for NewAttributes<'_, '_, TMsg> {
     where
         T: Serialize,
     {
-        let js_value = serde_wasm_bindgen::to_value(&value) //
-            .map_err(|err| crate::Error::caught_from_js(err.into()))?;
+        let js_value = serde_wasm_bindgen::to_value(&value).map_err(|err| {
+            crate::Error::new(ErrorKind::SerializeProperty(name.clone())).with_js_value(err.into())
+        })?;
         set_property(&self.velement.node, &*name, &js_value)?;
         self.velement.properties.insert(name, js_value);
         Ok(())
@@ -423,15 +650,9 @@ impl<TMsg: 'static> AttributesRenderer for NewAttributes<'_, '_, TMsg> {
     where
         D: EventDecoder<Msg = Self::Msg> + 'static,
     {
-        let tx = self.ctx.tx.clone();
-        let listener = EventListener::new(&self.velement.node, event_type, move |event| {
-            if let Some(msg) = decoder
-                .decode_event(AppEvent { event })
-                .expect_throw("failed to decode Event")
-            {
-                tx.unbounded_send(msg).unwrap_throw();
-            }
-        });
+        let listener = self
+            .ctx
+            .event_listener(&self.velement.node, event_type, decoder);
 
         self.velement.listeners.insert(event_type.into(), listener);
 
@@ -454,13 +675,73 @@ impl<TMsg: 'static> AttributesRenderer for NewAttributes<'_, '_, TMsg> {
         Ok(())
     }
 
+    fn drawing(&mut self, drawing: Drawing) -> Result<(), Self::Error> {
+        self.drawing.replace(drawing);
+        Ok(())
+    }
+
+    fn hook(&mut self, hook: Hook) -> Result<(), Self::Error> {
+        self.velement.hooks.push(hook);
+        Ok(())
+    }
+
+    fn node_ref(&mut self, node_ref: NodeRef) -> Result<(), Self::Error> {
+        self.velement.node_ref.replace(node_ref);
+        Ok(())
+    }
+
     fn end(self) -> Result<Self::Ok, Self::Error> {
         self.velement.apply_class()?;
         self.velement.apply_style()?;
+        if let Some(drawing) = self.drawing {
+            let canvas = Canvas::new(&self.velement.node, drawing)?;
+            self.velement.canvas.replace(canvas);
+        }
         Ok(())
     }
 }
 
+// ==== DecodeErrors ====
+
+type DecodeErrorHandler<TMsg> = Box<dyn FnMut(crate::Error) -> Option<TMsg>>;
+
+/// The handler of the errors occurred while decoding events, shared by
+/// the event listeners in an application.
+pub(crate) struct DecodeErrors<TMsg> {
+    handler: RefCell<DecodeErrorHandler<TMsg>>,
+    count: Cell<usize>,
+}
+
+impl<TMsg> DecodeErrors<TMsg> {
+    pub(crate) fn new() -> Self {
+        Self {
+            handler: RefCell::new(Box::new(|err| {
+                web::console::error_1(&err.into());
+                None
+            })),
+            count: Cell::new(0),
+        }
+    }
+
+    pub(crate) fn set_handler(&self, handler: DecodeErrorHandler<TMsg>) {
+        *self.handler.borrow_mut() = handler;
+    }
+
+    pub(crate) fn count(&self) -> usize {
+        self.count.get()
+    }
+
+    fn handle(&self, err: crate::Error) -> Option<TMsg> {
+        self.count.set(self.count.get() + 1);
+        match self.handler.try_borrow_mut() {
+            Ok(mut handler) => handler(err),
+            // The handler is being called reentrantly, e.g. an event dispatched
+            // synchronously from the handler itself.
+            Err(..) => None,
+        }
+    }
+}
+
 // ==== AppEvent ====
 
 struct AppEvent<'a> {
@@ -483,25 +764,25 @@ fn set_attribute(element: &web::Element, name: &str, value: &AttributeValue) ->
     match value {
         AttributeValue::String(value) => element
             .set_attribute(name, value)
-            .map_err(crate::Error::caught_from_js)?,
+            .map_err(crate::Error::dom("Element.setAttribute"))?,
         AttributeValue::Bool(true) => element
             .set_attribute(name, "")
-            .map_err(crate::Error::caught_from_js)?,
+            .map_err(crate::Error::dom("Element.setAttribute"))?,
         AttributeValue::Bool(false) => element
             .remove_attribute(name)
-            .map_err(crate::Error::caught_from_js)?,
+            .map_err(crate::Error::dom("Element.removeAttribute"))?,
     }
     Ok(())
 }
 
 fn set_property(element: &web::Element, name: &str, value: &JsValue) -> crate::Result<()> {
     js_sys::Reflect::set(element, &JsValue::from_str(name), value)
-        .map_err(crate::Error::caught_from_js)?;
+        .map_err(crate::Error::dom("Reflect.set"))?;
     Ok(())
 }
 
 fn remove_property(element: &web::Element, name: &str) -> crate::Result<()> {
     js_sys::Reflect::set(element, &JsValue::from_str(name), &JsValue::UNDEFINED)
-        .map_err(crate::Error::caught_from_js)?;
+        .map_err(crate::Error::dom("Reflect.set"))?;
     Ok(())
 }
@@ -0,0 +1,179 @@
+use crate::{
+    env::Env,
+    error::ErrorKind,
+    subscription::{storage_changes, StorageChanges},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{borrow::Cow, marker::PhantomData};
+
+/// The kind of Web Storage.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum StorageArea {
+    /// `window.localStorage`, which persists across sessions.
+    Local,
+    /// `window.sessionStorage`, which is cleared when the page session ends.
+    Session,
+}
+
+impl StorageArea {
+    pub(crate) fn storage(self, window: &web::Window) -> crate::Result<web::Storage> {
+        let storage = match self {
+            StorageArea::Local => window.local_storage(),
+            StorageArea::Session => window.session_storage(),
+        };
+        storage
+            .map_err(|err| crate::Error::new(ErrorKind::StorageUnavailable).with_js_value(err))?
+            .ok_or_else(|| crate::Error::new(ErrorKind::StorageUnavailable))
+    }
+}
+
+/// A handle to the Web Storage that stores the values of `T` encoded as JSON.
+///
+/// The keys are prefixed with the namespace, so that the handles with
+/// different namespaces do not conflict with each other.
+pub struct Storage<T> {
+    storage: web::Storage,
+    area: StorageArea,
+    namespace: Cow<'static, str>,
+    _marker: PhantomData<fn(T) -> T>,
+}
+
+impl<T> Clone for Storage<T> {
+    fn clone(&self) -> Self {
+        Self {
+            storage: self.storage.clone(),
+            area: self.area,
+            namespace: self.namespace.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Storage<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    pub(crate) fn new(
+        env: &Env,
+        area: StorageArea,
+        namespace: Cow<'static, str>,
+    ) -> crate::Result<Self> {
+        Ok(Self {
+            storage: area.storage(&env.window)?,
+            area,
+            namespace,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn area(&self) -> StorageArea {
+        self.area
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Return the stored value associated with the key.
+    pub fn get(&self, key: &str) -> crate::Result<Option<T>> {
+        let encoded = self
+            .storage
+            .get_item(&self.storage_key(key))
+            .map_err(crate::Error::dom("Storage.getItem"))?;
+        match encoded {
+            Some(encoded) => decode(&encoded).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Store the value associated with the key.
+    ///
+    /// An error of `ErrorKind::QuotaExceeded` is returned if the storage is full.
+    pub fn set(&self, key: &str, value: &T) -> crate::Result<()> {
+        let encoded = serde_json::to_string(value).map_err(|err| {
+            crate::Error::custom("failed to encode the storage item").with_source(err)
+        })?;
+        self.storage
+            .set_item(&self.storage_key(key), &encoded)
+            .map_err(crate::Error::dom("Storage.setItem"))
+    }
+
+    /// Remove the value associated with the key.
+    pub fn remove(&self, key: &str) -> crate::Result<()> {
+        self.storage
+            .remove_item(&self.storage_key(key))
+            .map_err(crate::Error::dom("Storage.removeItem"))
+    }
+
+    /// Return the keys in the namespace, without the prefix.
+    pub fn keys(&self) -> crate::Result<Vec<String>> {
+        let len = self
+            .storage
+            .length()
+            .map_err(crate::Error::dom("Storage.length"))?;
+        let mut keys = vec![];
+        for i in 0..len {
+            let key = self
+                .storage
+                .key(i)
+                .map_err(crate::Error::dom("Storage.key"))?;
+            if let Some(key) = key
+                .as_deref()
+                .and_then(|key| strip_namespace(&self.namespace, key))
+            {
+                keys.push(key.to_owned());
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Remove all values in the namespace.
+    ///
+    /// If the namespace is empty, the whole storage is cleared.
+    pub fn clear(&self) -> crate::Result<()> {
+        if self.namespace.is_empty() {
+            return self
+                .storage
+                .clear()
+                .map_err(crate::Error::dom("Storage.clear"));
+        }
+        for key in self.keys()? {
+            self.remove(&key)?;
+        }
+        Ok(())
+    }
+
+    /// Create a subscription that emits the changes in the namespace made by
+    /// other documents, such as the other tabs.
+    pub fn changes(&self) -> StorageChanges<T>
+    where
+        T: 'static,
+    {
+        storage_changes(self.area, self.namespace.clone())
+    }
+
+    fn storage_key(&self, key: &str) -> String {
+        if self.namespace.is_empty() {
+            key.to_owned()
+        } else {
+            format!("{}:{}", self.namespace, key)
+        }
+    }
+}
+
+/// Return the key without the prefix if it belongs to the namespace.
+pub(crate) fn strip_namespace<'a>(namespace: &str, key: &'a str) -> Option<&'a str> {
+    if namespace.is_empty() {
+        return Some(key);
+    }
+    let key = key.strip_prefix(namespace)?;
+    key.strip_prefix(':')
+}
+
+pub(crate) fn decode<T>(encoded: &str) -> crate::Result<T>
+where
+    T: DeserializeOwned,
+{
+    serde_json::from_str(encoded)
+        .map_err(|err| crate::Error::custom("failed to decode the storage item").with_source(err))
+}
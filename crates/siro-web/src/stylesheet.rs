@@ -0,0 +1,28 @@
+/// A constructable stylesheet, which can be shared among applications.
+///
+/// The stylesheet is applied to the shadow root where an application is
+/// mounted by `App::adopt_stylesheet`, so that the styles do not leak into
+/// the host page.
+#[derive(Debug, Clone)]
+pub struct StyleSheet {
+    pub(crate) sheet: web::CssStyleSheet,
+}
+
+impl StyleSheet {
+    pub(crate) fn new(css: &str) -> crate::Result<Self> {
+        let sheet = web::CssStyleSheet::new().map_err(crate::Error::dom("new CSSStyleSheet"))?;
+        sheet
+            .replace_sync(css)
+            .map_err(crate::Error::dom("CSSStyleSheet.replaceSync"))?;
+        Ok(Self { sheet })
+    }
+
+    /// Replace the content of this stylesheet.
+    ///
+    /// The change is reflected to all applications that adopt this stylesheet.
+    pub fn replace(&self, css: &str) -> crate::Result<()> {
+        self.sheet
+            .replace_sync(css)
+            .map_err(crate::Error::dom("CSSStyleSheet.replaceSync"))
+    }
+}
@@ -1,15 +1,72 @@
 mod animation_frames;
+mod batch;
+mod debounce;
+mod document_event;
+mod element_event;
+mod empty;
+mod event_stream;
+mod filter;
+mod filter_map;
+mod intersection_observer;
 mod interval;
+mod keyed;
 mod map;
+mod media_query;
+mod mutation_observer;
+mod online;
+mod outside_click;
+mod resize_observer;
+mod route_changes;
+mod shortcuts;
+mod state_stream;
+mod storage_changes;
+mod take_until;
+mod throttle;
+mod timeout;
+mod url_changes;
+mod visibility;
 mod window_event;
+mod window_size;
 
 pub use animation_frames::{animation_frames, AnimationFrames};
-pub use interval::{interval, Interval};
+pub use debounce::Debounce;
+pub use document_event::{document_event, DocumentEvent};
+pub use element_event::{element_event, ElementEvent};
+pub use empty::{empty, Empty};
+pub use filter::Filter;
+pub use filter_map::FilterMap;
+pub use intersection_observer::{
+    intersection_observer, IntersectionEntry, IntersectionObserver, IntersectionOptions,
+};
+#[allow(deprecated)]
+pub use interval::interval;
+pub use interval::{every, every_at_wall_clock, Every, Interval};
+pub use keyed::{keyed, Keyed, Subscriptions};
 pub use map::Map;
+pub use media_query::{media_query, prefers_color_scheme, MediaQuery};
+pub use mutation_observer::{
+    mutation_observer, MutationEntry, MutationKind, MutationObserver, MutationOptions,
+};
+pub use online::{online_status, OnlineStatus};
+pub use outside_click::{outside_click, OutsideClick};
+pub use resize_observer::{resize_observer, ResizeEntry, ResizeObserver};
+pub use route_changes::{route_changes, RouteChanges};
+pub use shortcuts::{shortcuts, Shortcuts};
+pub use storage_changes::{storage_changes, StorageChange, StorageChanges};
+pub use take_until::TakeUntil;
+pub use throttle::Throttle;
+pub use timeout::{timeout, Timeout};
+pub use url_changes::{url_changes, UrlChanges};
+pub use visibility::{visibility_changes, VisibilityChanges};
 pub use window_event::{window_event, WindowEvent};
+pub use window_size::{window_size, WindowSize};
+
+use url_changes::UrlChangesStream;
+pub(crate) use url_changes::URL_CHANGE_EVENT;
 
 use crate::env::Env;
 use futures::stream::FusedStream;
+use std::{convert::TryFrom, time::Duration};
 
 pub trait Subscription {
     type Msg: 'static;
@@ -18,6 +75,12 @@ pub trait Subscription {
     fn subscribe(self, env: &Env) -> crate::Result<Self::Stream>;
 
     /// Map the message type to another one.
+    ///
+    /// The returned subscription can be compared only if the function can,
+    /// e.g. a function pointer such as `Msg::Tick as fn(_) -> _`, so that
+    /// `run` resubscribes whenever the function changes.  The subscriptions
+    /// whose closures capture the model state should be managed by
+    /// [`Subscriptions`] with a key containing the captured values instead.
     fn map<F, TMsg>(self, f: F) -> Map<Self, F>
     where
         Self: Sized,
@@ -26,4 +89,59 @@ pub trait Subscription {
     {
         Map::new(self, f)
     }
+
+    /// Map the messages to another type, and drop them if `f` returns `None`.
+    ///
+    /// The function is compared as in `map`.
+    fn filter_map<F, TMsg>(self, f: F) -> FilterMap<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(Self::Msg) -> Option<TMsg>,
+        TMsg: 'static,
+    {
+        FilterMap::new(self, f)
+    }
+
+    /// Drop the messages that do not satisfy the predicate.
+    ///
+    /// The predicate is compared as in `map`.
+    fn filter<F>(self, f: F) -> Filter<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Msg) -> bool,
+    {
+        Filter::new(self, f)
+    }
+
+    /// Emit only the latest message after no messages are emitted for the duration.
+    fn debounce(self, duration: Duration) -> Debounce<Self>
+    where
+        Self: Sized,
+    {
+        Debounce::new(self, duration)
+    }
+
+    /// Emit at most one message per the duration.
+    ///
+    /// The first message is emitted immediately, and the latest one received
+    /// during the period is emitted at the end of it.
+    fn throttle(self, duration: Duration) -> Throttle<Self>
+    where
+        Self: Sized,
+    {
+        Throttle::new(self, duration)
+    }
+
+    /// Stop emitting messages when the `notifier` emits its first message.
+    fn take_until<N>(self, notifier: N) -> TakeUntil<Self, N>
+    where
+        Self: Sized,
+        N: Subscription,
+    {
+        TakeUntil::new(self, notifier)
+    }
+}
+
+fn duration_to_millis(duration: Duration) -> i32 {
+    i32::try_from(duration.as_millis()).unwrap_or(i32::MAX)
 }
@@ -15,6 +15,7 @@ pub fn animation_frames() -> AnimationFrames {
     AnimationFrames { _p: () }
 }
 
+#[derive(Clone, PartialEq)]
 pub struct AnimationFrames {
     _p: (),
 }
@@ -0,0 +1,60 @@
+use super::Subscription;
+use crate::env::Env;
+use futures::stream::{select, select_all, Select, SelectAll};
+
+// The tuples of subscriptions that share the message type are merged into a
+// subscription, e.g. `(window_event("mousedown"), window_event("mouseup"))`.
+macro_rules! impl_subscription_for_tuples {
+    ( $H:ident, $( $T:ident ),+ ) => {
+        impl< $H, $( $T ),+ > Subscription for ( $H, $( $T ),+ )
+        where
+            $H: Subscription,
+            ( $( $T, )+ ): Subscription<Msg = $H::Msg>,
+        {
+            type Msg = $H::Msg;
+            type Stream = Select<$H::Stream, <( $( $T, )+ ) as Subscription>::Stream>;
+
+            #[allow(non_snake_case)]
+            fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+                let ( $H, $( $T ),+ ) = self;
+                Ok(select($H.subscribe(env)?, ( $( $T, )+ ).subscribe(env)?))
+            }
+        }
+
+        impl_subscription_for_tuples!( $( $T ),+ );
+    };
+
+    ( $S:ident ) => {
+        impl< $S > Subscription for ( $S, )
+        where
+            $S: Subscription,
+        {
+            type Msg = $S::Msg;
+            type Stream = $S::Stream;
+
+            fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+                self.0.subscribe(env)
+            }
+        }
+    };
+}
+
+impl_subscription_for_tuples!(S1, S2, S3, S4, S5, S6, S7, S8, S9, S10, S11, S12);
+
+/// The subscriptions in a `Vec` are merged into a subscription.
+impl<S> Subscription for Vec<S>
+where
+    S: Subscription,
+    S::Stream: Unpin,
+{
+    type Msg = S::Msg;
+    type Stream = SelectAll<S::Stream>;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        let streams = self
+            .into_iter()
+            .map(|subscription| subscription.subscribe(env))
+            .collect::<crate::Result<Vec<_>>>()?;
+        Ok(select_all(streams))
+    }
+}
@@ -0,0 +1,107 @@
+use super::{timeout::TimeoutStream, Subscription};
+use crate::env::Env;
+use futures::prelude::*;
+use futures::{
+    stream::{FusedStream, Stream},
+    task::{self, Poll},
+};
+use std::{pin::Pin, time::Duration};
+
+#[derive(Clone, PartialEq)]
+pub struct Debounce<S> {
+    subscription: S,
+    duration: Duration,
+}
+
+impl<S> Debounce<S> {
+    pub(super) fn new(subscription: S, duration: Duration) -> Self {
+        Self {
+            subscription,
+            duration,
+        }
+    }
+}
+
+impl<S> Subscription for Debounce<S>
+where
+    S: Subscription,
+    S::Stream: Unpin,
+    S::Msg: Unpin,
+{
+    type Msg = S::Msg;
+    type Stream = DebounceStream<S::Stream>;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        Ok(DebounceStream {
+            stream: self.subscription.subscribe(env)?,
+            window: env.window.clone(),
+            duration: self.duration,
+            pending: None,
+            timer: None,
+        })
+    }
+}
+
+pub struct DebounceStream<St: Stream> {
+    stream: St,
+    window: web::Window,
+    duration: Duration,
+    pending: Option<St::Item>,
+    timer: Option<TimeoutStream>,
+}
+
+impl<St> Stream for DebounceStream<St>
+where
+    St: FusedStream + Unpin,
+    St::Item: Unpin,
+{
+    type Item = St::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.get_mut();
+
+        // Restart the timer every time a message arrives, keeping only the latest one.
+        while !me.stream.is_terminated() {
+            match me.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(msg)) => {
+                    me.pending = Some(msg);
+                    me.timer = TimeoutStream::new(&me.window, me.duration).ok();
+                }
+                Poll::Ready(None) | Poll::Pending => break,
+            }
+        }
+
+        if me.pending.is_none() {
+            return if me.stream.is_terminated() {
+                Poll::Ready(None)
+            } else {
+                Poll::Pending
+            };
+        }
+
+        // The pending message is flushed immediately if the inner stream has
+        // been terminated or the timer could not be started.
+        let expired = match me.timer {
+            Some(ref mut timer) if !me.stream.is_terminated() => {
+                timer.poll_next_unpin(cx).is_ready()
+            }
+            _ => true,
+        };
+        if expired {
+            me.timer = None;
+            Poll::Ready(me.pending.take())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<St> FusedStream for DebounceStream<St>
+where
+    St: FusedStream + Unpin,
+    St::Item: Unpin,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.pending.is_none()
+    }
+}
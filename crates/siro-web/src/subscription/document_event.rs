@@ -0,0 +1,47 @@
+use super::{event_stream::EventStream, Subscription};
+use crate::env::Env;
+use serde::Deserialize;
+use std::{borrow::Cow, marker::PhantomData};
+
+/// Create a subscription that listens to the events on `document`.
+pub fn document_event<T>(event_type: impl Into<Cow<'static, str>>) -> DocumentEvent<T>
+where
+    T: for<'de> Deserialize<'de> + 'static,
+{
+    DocumentEvent {
+        event_type: event_type.into(),
+        _marker: PhantomData,
+    }
+}
+
+pub struct DocumentEvent<T> {
+    event_type: Cow<'static, str>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for DocumentEvent<T> {
+    fn clone(&self) -> Self {
+        Self {
+            event_type: self.event_type.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> PartialEq for DocumentEvent<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.event_type == other.event_type
+    }
+}
+
+impl<T> Subscription for DocumentEvent<T>
+where
+    T: for<'de> Deserialize<'de> + 'static,
+{
+    type Msg = T;
+    type Stream = EventStream<T>;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        Ok(EventStream::new(&env.document, self.event_type, |_| true))
+    }
+}
@@ -0,0 +1,62 @@
+use super::{event_stream::EventStream, Subscription};
+use crate::{env::Env, error::ErrorKind};
+use serde::Deserialize;
+use std::{borrow::Cow, marker::PhantomData};
+
+/// Create a subscription that listens to the events on the element
+/// specified by the selector.
+///
+/// The element is looked up when subscribing, and it is an error if no
+/// element matches the selector.
+pub fn element_event<T>(
+    selector: impl Into<Cow<'static, str>>,
+    event_type: impl Into<Cow<'static, str>>,
+) -> ElementEvent<T>
+where
+    T: for<'de> Deserialize<'de> + 'static,
+{
+    ElementEvent {
+        selector: selector.into(),
+        event_type: event_type.into(),
+        _marker: PhantomData,
+    }
+}
+
+pub struct ElementEvent<T> {
+    selector: Cow<'static, str>,
+    event_type: Cow<'static, str>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for ElementEvent<T> {
+    fn clone(&self) -> Self {
+        Self {
+            selector: self.selector.clone(),
+            event_type: self.event_type.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> PartialEq for ElementEvent<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.selector == other.selector && self.event_type == other.event_type
+    }
+}
+
+impl<T> Subscription for ElementEvent<T>
+where
+    T: for<'de> Deserialize<'de> + 'static,
+{
+    type Msg = T;
+    type Stream = EventStream<T>;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        let element = env
+            .document
+            .query_selector(&self.selector)
+            .map_err(crate::Error::dom("Document.querySelector"))?
+            .ok_or_else(|| crate::Error::new(ErrorKind::MissingElement(self.selector.clone())))?;
+        Ok(EventStream::new(&element, self.event_type, |_| true))
+    }
+}
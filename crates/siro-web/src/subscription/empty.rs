@@ -0,0 +1,39 @@
+use super::Subscription;
+use crate::env::Env;
+use futures::stream;
+use std::marker::PhantomData;
+
+/// Create a subscription that never emits any messages.
+#[inline]
+pub fn empty<TMsg: 'static>() -> Empty<TMsg> {
+    Empty {
+        _marker: PhantomData,
+    }
+}
+
+pub struct Empty<TMsg> {
+    _marker: PhantomData<fn() -> TMsg>,
+}
+
+impl<TMsg> Clone for Empty<TMsg> {
+    fn clone(&self) -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<TMsg> PartialEq for Empty<TMsg> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<TMsg: 'static> Subscription for Empty<TMsg> {
+    type Msg = TMsg;
+    type Stream = stream::Empty<TMsg>;
+
+    fn subscribe(self, _: &Env) -> crate::Result<Self::Stream> {
+        Ok(stream::empty())
+    }
+}
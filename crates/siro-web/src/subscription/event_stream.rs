@@ -0,0 +1,67 @@
+use futures::prelude::*;
+use futures::{
+    channel::mpsc,
+    stream::{FusedStream, Stream},
+    task::{self, Poll},
+};
+use gloo_events::EventListener;
+use serde::Deserialize;
+use std::{borrow::Cow, pin::Pin};
+use wasm_bindgen::prelude::*;
+
+/// The stream of events deserialized from a listener on the `EventTarget`.
+pub struct EventStream<T> {
+    rx: mpsc::UnboundedReceiver<T>,
+    _listener: EventListener,
+}
+
+impl<T> EventStream<T>
+where
+    T: for<'de> Deserialize<'de> + 'static,
+{
+    /// Listen to the events that satisfy the predicate.
+    pub(super) fn new<F>(
+        target: &web::EventTarget,
+        event_type: Cow<'static, str>,
+        predicate: F,
+    ) -> Self
+    where
+        F: Fn(&web::Event) -> bool + 'static,
+    {
+        let (tx, rx) = mpsc::unbounded();
+
+        let listener = EventListener::new(target, event_type, move |event| {
+            if !predicate(event) {
+                return;
+            }
+            let event: &JsValue = event.as_ref();
+            let de = serde_wasm_bindgen::Deserializer::from(event.clone());
+            if let Ok(msg) = T::deserialize(de) {
+                tx.unbounded_send(msg).unwrap_throw();
+            }
+        });
+
+        Self {
+            rx,
+            _listener: listener,
+        }
+    }
+}
+
+impl<T> Stream for EventStream<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().rx.poll_next_unpin(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rx.size_hint()
+    }
+}
+
+impl<T> FusedStream for EventStream<T> {
+    fn is_terminated(&self) -> bool {
+        self.rx.is_terminated()
+    }
+}
@@ -0,0 +1,75 @@
+use super::Subscription;
+use crate::env::Env;
+use futures::prelude::*;
+use futures::{
+    stream::{FusedStream, Stream},
+    task::{self, Poll},
+};
+use std::pin::Pin;
+
+#[derive(Clone, PartialEq)]
+pub struct Filter<S, F> {
+    subscription: S,
+    f: F,
+}
+
+impl<S, F> Filter<S, F> {
+    pub(super) fn new(subscription: S, f: F) -> Self {
+        Self { subscription, f }
+    }
+}
+
+impl<S, F> Subscription for Filter<S, F>
+where
+    S: Subscription,
+    S::Stream: Unpin,
+    F: FnMut(&S::Msg) -> bool + Unpin,
+{
+    type Msg = S::Msg;
+    type Stream = FilterStream<S::Stream, F>;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        Ok(FilterStream {
+            stream: self.subscription.subscribe(env)?,
+            f: self.f,
+        })
+    }
+}
+
+pub struct FilterStream<St, F> {
+    stream: St,
+    f: F,
+}
+
+impl<St, F> Stream for FilterStream<St, F>
+where
+    St: Stream + Unpin,
+    F: FnMut(&St::Item) -> bool + Unpin,
+{
+    type Item = St::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.get_mut();
+        loop {
+            match futures::ready!(me.stream.poll_next_unpin(cx)) {
+                Some(msg) if (me.f)(&msg) => return Poll::Ready(Some(msg)),
+                Some(..) => (),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.stream.size_hint().1)
+    }
+}
+
+impl<St, F> FusedStream for FilterStream<St, F>
+where
+    St: FusedStream + Unpin,
+    F: FnMut(&St::Item) -> bool + Unpin,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated()
+    }
+}
@@ -0,0 +1,79 @@
+use super::Subscription;
+use crate::env::Env;
+use futures::prelude::*;
+use futures::{
+    stream::{FusedStream, Stream},
+    task::{self, Poll},
+};
+use std::pin::Pin;
+
+#[derive(Clone, PartialEq)]
+pub struct FilterMap<S, F> {
+    subscription: S,
+    f: F,
+}
+
+impl<S, F> FilterMap<S, F> {
+    pub(super) fn new(subscription: S, f: F) -> Self {
+        Self { subscription, f }
+    }
+}
+
+impl<S, F, TMsg> Subscription for FilterMap<S, F>
+where
+    S: Subscription,
+    S::Stream: Unpin,
+    F: FnMut(S::Msg) -> Option<TMsg> + Unpin,
+    TMsg: 'static,
+{
+    type Msg = TMsg;
+    type Stream = FilterMapStream<S::Stream, F>;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        Ok(FilterMapStream {
+            stream: self.subscription.subscribe(env)?,
+            f: self.f,
+        })
+    }
+}
+
+pub struct FilterMapStream<St, F> {
+    stream: St,
+    f: F,
+}
+
+impl<St, F, TMsg> Stream for FilterMapStream<St, F>
+where
+    St: Stream + Unpin,
+    F: FnMut(St::Item) -> Option<TMsg> + Unpin,
+{
+    type Item = TMsg;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.get_mut();
+        loop {
+            match futures::ready!(me.stream.poll_next_unpin(cx)) {
+                Some(msg) => {
+                    if let Some(msg) = (me.f)(msg) {
+                        return Poll::Ready(Some(msg));
+                    }
+                }
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.stream.size_hint().1)
+    }
+}
+
+impl<St, F, TMsg> FusedStream for FilterMapStream<St, F>
+where
+    St: FusedStream + Unpin,
+    F: FnMut(St::Item) -> Option<TMsg> + Unpin,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated()
+    }
+}
@@ -0,0 +1,142 @@
+use super::Subscription;
+use crate::{env::Env, error::ErrorKind};
+use futures::prelude::*;
+use futures::{
+    channel::mpsc,
+    stream::{FusedStream, Stream},
+    task::{self, Poll},
+};
+use std::{borrow::Cow, pin::Pin};
+use wasm_bindgen::prelude::*;
+
+/// Create a subscription that observes the intersection of the element with
+/// the specified id and the viewport by using `IntersectionObserver`.
+pub fn intersection_observer(
+    target_id: impl Into<Cow<'static, str>>,
+    options: IntersectionOptions,
+) -> IntersectionObserver {
+    IntersectionObserver {
+        target_id: target_id.into(),
+        options,
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct IntersectionObserver {
+    target_id: Cow<'static, str>,
+    options: IntersectionOptions,
+}
+
+/// The options of `intersection_observer`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IntersectionOptions {
+    root_margin: Option<Cow<'static, str>>,
+    thresholds: Vec<f64>,
+}
+
+impl IntersectionOptions {
+    /// Create an `IntersectionOptions` with the default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the margin around the viewport, such as `"200px 0px"`.
+    pub fn root_margin(mut self, root_margin: impl Into<Cow<'static, str>>) -> Self {
+        self.root_margin = Some(root_margin.into());
+        self
+    }
+
+    /// Add a ratio of intersection at which the entries are emitted.
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.thresholds.push(threshold);
+        self
+    }
+}
+
+/// A change of the intersection of the observed element.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct IntersectionEntry {
+    pub is_intersecting: bool,
+    pub intersection_ratio: f64,
+    pub time: f64,
+}
+
+impl Subscription for IntersectionObserver {
+    type Msg = IntersectionEntry;
+    type Stream = IntersectionObserverStream;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        let target = env
+            .get_element_by_id(&self.target_id)
+            .ok_or_else(|| crate::Error::new(ErrorKind::MissingElement(self.target_id.clone())))?;
+
+        let (tx, rx) = mpsc::unbounded();
+
+        let cb = Closure::wrap(Box::new(move |entries: js_sys::Array| {
+            for entry in entries.iter() {
+                let entry: web::IntersectionObserverEntry = entry.unchecked_into();
+                tx.unbounded_send(IntersectionEntry {
+                    is_intersecting: entry.is_intersecting(),
+                    intersection_ratio: entry.intersection_ratio(),
+                    time: entry.time(),
+                })
+                .unwrap_throw();
+            }
+        }) as Box<dyn FnMut(js_sys::Array)>);
+
+        let init = web::IntersectionObserverInit::new();
+        if let Some(ref root_margin) = self.options.root_margin {
+            init.set_root_margin(root_margin);
+        }
+        if !self.options.thresholds.is_empty() {
+            let thresholds: js_sys::Array = self
+                .options
+                .thresholds
+                .iter()
+                .map(|&threshold| JsValue::from_f64(threshold))
+                .collect();
+            init.set_threshold(&thresholds);
+        }
+
+        let observer =
+            web::IntersectionObserver::new_with_options(cb.as_ref().unchecked_ref(), &init)
+                .map_err(crate::Error::dom("new IntersectionObserver"))?;
+        observer.observe(&target);
+
+        Ok(IntersectionObserverStream {
+            rx,
+            observer,
+            _cb: cb,
+        })
+    }
+}
+
+pub struct IntersectionObserverStream {
+    rx: mpsc::UnboundedReceiver<IntersectionEntry>,
+    observer: web::IntersectionObserver,
+    _cb: Closure<dyn FnMut(js_sys::Array)>,
+}
+
+impl Drop for IntersectionObserverStream {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+impl Stream for IntersectionObserverStream {
+    type Item = IntersectionEntry;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().rx.poll_next_unpin(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rx.size_hint()
+    }
+}
+
+impl FusedStream for IntersectionObserverStream {
+    fn is_terminated(&self) -> bool {
+        self.rx.is_terminated()
+    }
+}
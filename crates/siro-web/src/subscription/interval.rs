@@ -1,4 +1,4 @@
-use super::Subscription;
+use super::{duration_to_millis, Subscription};
 use crate::env::Env;
 use futures::prelude::*;
 use futures::{
@@ -6,15 +6,23 @@ use futures::{
     stream::{FusedStream, Stream},
     task::{self, Poll},
 };
-use std::pin::Pin;
+use std::{
+    cell::{Cell, RefCell},
+    pin::Pin,
+    rc::Rc,
+    time::Duration,
+};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast as _;
 
+/// Create a subscription that emits `()` at the specified period in milliseconds.
+#[deprecated(note = "use `every` instead")]
 #[inline]
 pub fn interval(timeout: i32) -> Interval {
     Interval { timeout }
 }
 
+#[derive(Clone, PartialEq)]
 pub struct Interval {
     timeout: i32,
 }
@@ -23,60 +31,203 @@ impl Subscription for Interval {
     type Msg = ();
     type Stream = IntervalStream;
 
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        let period = Duration::from_millis(self.timeout.max(0) as u64);
+        Ok(IntervalStream {
+            inner: every(period).subscribe(env)?,
+        })
+    }
+}
+
+pub struct IntervalStream {
+    inner: EveryStream,
+}
+
+impl Stream for IntervalStream {
+    type Item = ();
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut()
+            .inner
+            .poll_next_unpin(cx)
+            .map(|tick| tick.map(|_| ()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl FusedStream for IntervalStream {
+    fn is_terminated(&self) -> bool {
+        self.inner.is_terminated()
+    }
+}
+
+/// Create a subscription that emits the current time at the specified period.
+///
+/// The emitted value is the number of milliseconds elapsed since the UNIX
+/// epoch, as returned from `Date.now()`.
+#[inline]
+pub fn every(period: Duration) -> Every {
+    Every {
+        period,
+        aligned: false,
+    }
+}
+
+/// Create a subscription that emits the current time at the multiples of
+/// the specified period on the wall clock.
+///
+/// For example, `every_at_wall_clock(Duration::from_secs(1))` ticks at the
+/// beginning of every second, which is suitable for displaying clocks.
+/// The emitted value is the same as `every`.
+#[inline]
+pub fn every_at_wall_clock(period: Duration) -> Every {
+    Every {
+        period,
+        aligned: true,
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct Every {
+    period: Duration,
+    aligned: bool,
+}
+
+impl Subscription for Every {
+    type Msg = f64;
+    type Stream = EveryStream;
+
     fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
         let (tx, rx) = mpsc::unbounded();
+        let period = duration_to_millis(self.period).max(1);
 
-        let cb = Closure::wrap(Box::new(move || {
-            tx.unbounded_send(()).unwrap_throw();
-        }) as Box<dyn FnMut()>);
+        let inner = if self.aligned {
+            subscribe_aligned(env, period, tx)?
+        } else {
+            let cb = Closure::wrap(Box::new(move || {
+                tx.unbounded_send(js_sys::Date::now()).unwrap_throw();
+            }) as Box<dyn FnMut()>);
 
-        let id = env
-            .window
-            .set_interval_with_callback_and_timeout_and_arguments_0(
-                cb.as_ref().unchecked_ref(),
-                self.timeout,
-            )
-            .map_err(crate::Error::caught_from_js)?;
+            let id = env
+                .window
+                .set_interval_with_callback_and_timeout_and_arguments_0(
+                    cb.as_ref().unchecked_ref(),
+                    period,
+                )
+                .map_err(crate::Error::dom("setInterval"))?;
 
-        Ok(IntervalStream {
-            rx,
-            inner: Some(Inner {
+            Inner::Interval {
                 window: env.window.clone(),
                 id,
                 _cb: cb,
-            }),
+            }
+        };
+
+        Ok(EveryStream {
+            rx,
+            inner: Some(inner),
         })
     }
 }
 
-pub struct IntervalStream {
-    rx: mpsc::UnboundedReceiver<()>,
+/// Tick at the wall clock by chaining `setTimeout`, so that the drift of
+/// timers does not accumulate.
+fn subscribe_aligned(
+    env: &Env,
+    period: i32,
+    tx: mpsc::UnboundedSender<f64>,
+) -> crate::Result<Inner> {
+    let id = Rc::new(Cell::new(0));
+    let slot: Slot = Rc::new(RefCell::new(None));
+
+    let cb = Closure::wrap(Box::new({
+        let window = env.window.clone();
+        let id = id.clone();
+        let slot = Rc::downgrade(&slot);
+        move || {
+            let now = js_sys::Date::now();
+            tx.unbounded_send(now).unwrap_throw();
+
+            // The timer fires at or after the boundary, so the next one is
+            // the first boundary after the current time.
+            let next = ((now / period as f64).floor() + 1.0) * period as f64;
+            let delay = (next - now).ceil() as i32;
+
+            if let Some(slot) = slot.upgrade() {
+                if let Some(cb) = &*slot.borrow() {
+                    if let Ok(handle) = set_timeout(&window, cb, delay) {
+                        id.set(handle);
+                    }
+                }
+            }
+        }
+    }) as Box<dyn FnMut()>);
+
+    let now = js_sys::Date::now();
+    let delay = period - (now % period as f64) as i32;
+    id.set(set_timeout(&env.window, &cb, delay)?);
+    slot.replace(Some(cb));
+
+    Ok(Inner::Aligned {
+        window: env.window.clone(),
+        id,
+        _slot: slot,
+    })
+}
+
+fn set_timeout(window: &web::Window, cb: &Closure<dyn FnMut()>, delay: i32) -> crate::Result<i32> {
+    window
+        .set_timeout_with_callback_and_timeout_and_arguments_0(cb.as_ref().unchecked_ref(), delay)
+        .map_err(crate::Error::dom("setTimeout"))
+}
+
+/// The shared slot of the callback, which schedules itself again.
+type Slot = Rc<RefCell<Option<Closure<dyn FnMut()>>>>;
+
+pub struct EveryStream {
+    rx: mpsc::UnboundedReceiver<f64>,
     inner: Option<Inner>,
 }
 
-struct Inner {
-    window: web::Window,
-    id: i32,
-    _cb: Closure<dyn FnMut()>,
+enum Inner {
+    Interval {
+        window: web::Window,
+        id: i32,
+        _cb: Closure<dyn FnMut()>,
+    },
+    Aligned {
+        window: web::Window,
+        id: Rc<Cell<i32>>,
+        _slot: Slot,
+    },
 }
 
-impl IntervalStream {
+impl EveryStream {
     fn unsubscribe(&mut self) -> Result<(), JsValue> {
-        if let Some(inner) = self.inner.take() {
-            inner.window.clear_interval_with_handle(inner.id);
+        match self.inner.take() {
+            Some(Inner::Interval { window, id, .. }) => {
+                window.clear_interval_with_handle(id);
+            }
+            Some(Inner::Aligned { window, id, .. }) => {
+                window.clear_timeout_with_handle(id.get());
+            }
+            None => (),
         }
         Ok(())
     }
 }
 
-impl Drop for IntervalStream {
+impl Drop for EveryStream {
     fn drop(&mut self) {
         let _ = self.unsubscribe();
     }
 }
 
-impl Stream for IntervalStream {
-    type Item = ();
+impl Stream for EveryStream {
+    type Item = f64;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
         self.get_mut().rx.poll_next_unpin(cx)
@@ -87,7 +238,7 @@ impl Stream for IntervalStream {
     }
 }
 
-impl FusedStream for IntervalStream {
+impl FusedStream for EveryStream {
     fn is_terminated(&self) -> bool {
         self.rx.is_terminated()
     }
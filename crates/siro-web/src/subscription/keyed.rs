@@ -0,0 +1,150 @@
+use super::Subscription;
+use crate::env::Env;
+use futures::{
+    channel::mpsc,
+    future::{AbortHandle, Abortable},
+    prelude::*,
+};
+use rustc_hash::FxHashMap;
+use std::{hash::Hash, pin::Pin};
+
+type BoxStream<TMsg> = Pin<Box<dyn Stream<Item = TMsg>>>;
+type Subscribe<TMsg> = Box<dyn FnOnce(&Env) -> crate::Result<BoxStream<TMsg>>>;
+
+/// Associate a subscription with the key that identifies it in `Subscriptions`.
+pub fn keyed<K, S>(key: K, subscription: S) -> Keyed<K, S::Msg>
+where
+    S: Subscription + 'static,
+    S::Stream: 'static,
+{
+    Keyed {
+        key,
+        subscribe: Box::new(move |env| {
+            let stream = subscription.subscribe(env)?;
+            Ok(Box::pin(stream) as BoxStream<S::Msg>)
+        }),
+    }
+}
+
+/// A subscription associated with a key, created by `keyed`.
+pub struct Keyed<K, TMsg> {
+    key: K,
+    subscribe: Subscribe<TMsg>,
+}
+
+/// A manager of the running subscriptions that are keyed by their identities.
+///
+/// Every call of `update` receives the set of subscriptions that should be
+/// running, and only the difference from the running ones is applied:
+/// the subscriptions with new keys are started, and the ones whose keys are
+/// no longer present are dropped.  A subscription is identified solely by
+/// its key, so it is not restarted even if its parameters have changed.
+///
+/// The messages from the subscriptions are sent to the `App` that created
+/// this manager.  All of the subscriptions are dropped with the manager.
+pub struct Subscriptions<'env, K, TMsg: 'static> {
+    env: &'env Env,
+    mountpoint: web::Node,
+    tx: mpsc::UnboundedSender<TMsg>,
+    running: FxHashMap<K, Running>,
+}
+
+impl<'env, K, TMsg: 'static> Subscriptions<'env, K, TMsg>
+where
+    K: Eq + Hash,
+{
+    pub(crate) fn new(
+        env: &'env Env,
+        mountpoint: web::Node,
+        tx: mpsc::UnboundedSender<TMsg>,
+    ) -> Self {
+        Self {
+            env,
+            mountpoint,
+            tx,
+            running: FxHashMap::default(),
+        }
+    }
+
+    /// Replace the running subscriptions with the specified ones.
+    ///
+    /// If the same key appears more than once, the first one is used.
+    pub fn update<I>(&mut self, subscriptions: I) -> crate::Result<()>
+    where
+        I: IntoIterator<Item = Keyed<K, TMsg>>,
+    {
+        let mut running = FxHashMap::default();
+        for Keyed { key, subscribe } in subscriptions {
+            if running.contains_key(&key) {
+                continue;
+            }
+            let handle = match self.running.remove(&key) {
+                Some(handle) => handle,
+                None => match subscribe(&self.env.scoped(&self.mountpoint)) {
+                    Ok(stream) => self.spawn(stream),
+                    Err(err) => {
+                        self.running.extend(running);
+                        return Err(err);
+                    }
+                },
+            };
+            running.insert(key, handle);
+        }
+
+        // The subscriptions left here are stopped by dropping their handles.
+        self.running = running;
+
+        Ok(())
+    }
+
+    /// Return whether the subscription with the key is running.
+    pub fn contains(&self, key: &K) -> bool {
+        self.running.contains_key(key)
+    }
+
+    /// Return the number of running subscriptions.
+    pub fn len(&self) -> usize {
+        self.running.len()
+    }
+
+    /// Return whether no subscriptions are running.
+    pub fn is_empty(&self) -> bool {
+        self.running.is_empty()
+    }
+
+    /// Stop all of the running subscriptions.
+    pub fn clear(&mut self) {
+        self.running.clear();
+    }
+
+    fn spawn(&self, mut stream: BoxStream<TMsg>) -> Running {
+        let (handle, registration) = AbortHandle::new_pair();
+        let tx = self.tx.clone();
+        let task = Abortable::new(
+            async move {
+                while let Some(msg) = stream.next().await {
+                    if tx.unbounded_send(msg).is_err() {
+                        break;
+                    }
+                }
+            },
+            registration,
+        );
+        wasm_bindgen_futures::spawn_local(task.map(|_| ()));
+        Running { handle }
+    }
+}
+
+/// The handle of a running subscription.
+///
+/// The task is aborted on drop, and then the stream is released along with
+/// the task.
+struct Running {
+    handle: AbortHandle,
+}
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
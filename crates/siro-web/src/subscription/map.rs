@@ -2,6 +2,7 @@ use super::Subscription;
 use crate::env::Env;
 use futures::prelude::*;
 
+#[derive(Clone, PartialEq)]
 pub struct Map<S, F> {
     subscription: S,
     f: F,
@@ -0,0 +1,42 @@
+use super::{state_stream::StateStream, Map, Subscription};
+use crate::env::{ColorScheme, Env};
+use std::borrow::Cow;
+
+/// Create a subscription that emits whether the document matches the media
+/// query on subscribe and whenever it is changed.
+#[inline]
+pub fn media_query(query: impl Into<Cow<'static, str>>) -> MediaQuery {
+    MediaQuery {
+        query: query.into(),
+    }
+}
+
+/// Create a subscription that emits the color scheme preferred by the user
+/// on subscribe and whenever it is changed.
+pub fn prefers_color_scheme() -> Map<MediaQuery, fn(bool) -> ColorScheme> {
+    media_query(ColorScheme::DARK_QUERY).map(|dark| {
+        if dark {
+            ColorScheme::Dark
+        } else {
+            ColorScheme::Light
+        }
+    })
+}
+
+#[derive(Clone, PartialEq)]
+pub struct MediaQuery {
+    query: Cow<'static, str>,
+}
+
+impl Subscription for MediaQuery {
+    type Msg = bool;
+    type Stream = StateStream<bool>;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        let list = env.match_media(&self.query)?;
+        let reader = list.clone();
+        Ok(StateStream::new(&[(&list, "change")], move || {
+            reader.matches()
+        }))
+    }
+}
@@ -0,0 +1,202 @@
+use super::Subscription;
+use crate::{env::Env, error::ErrorKind};
+use futures::prelude::*;
+use futures::{
+    channel::mpsc,
+    stream::{FusedStream, Stream},
+    task::{self, Poll},
+};
+use std::{borrow::Cow, pin::Pin};
+use wasm_bindgen::prelude::*;
+
+/// Create a subscription that observes the changes of the DOM tree rooted
+/// at the element with the specified id by using `MutationObserver`.
+pub fn mutation_observer(
+    target_id: impl Into<Cow<'static, str>>,
+    options: MutationOptions,
+) -> MutationObserver {
+    MutationObserver {
+        target_id: target_id.into(),
+        options,
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct MutationObserver {
+    target_id: Cow<'static, str>,
+    options: MutationOptions,
+}
+
+/// The options of `mutation_observer`, which specify the kinds of observed changes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MutationOptions {
+    child_list: bool,
+    attributes: bool,
+    character_data: bool,
+    subtree: bool,
+    old_value: bool,
+    attribute_filter: Vec<Cow<'static, str>>,
+}
+
+impl MutationOptions {
+    /// Create a `MutationOptions` that observes nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Observe the addition and removal of the child nodes.
+    pub fn child_list(mut self) -> Self {
+        self.child_list = true;
+        self
+    }
+
+    /// Observe the changes of the attributes.
+    pub fn attributes(mut self) -> Self {
+        self.attributes = true;
+        self
+    }
+
+    /// Observe only the changes of the specified attribute.
+    ///
+    /// This method implies `attributes`.
+    pub fn attribute(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.attributes = true;
+        self.attribute_filter.push(name.into());
+        self
+    }
+
+    /// Observe the changes of the text contents.
+    pub fn character_data(mut self) -> Self {
+        self.character_data = true;
+        self
+    }
+
+    /// Observe the descendant nodes as well as the target element.
+    pub fn subtree(mut self) -> Self {
+        self.subtree = true;
+        self
+    }
+
+    /// Record the previous values of the changed attributes and text contents.
+    pub fn old_value(mut self) -> Self {
+        self.old_value = true;
+        self
+    }
+}
+
+/// The kind of mutation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MutationKind {
+    ChildList,
+    Attributes,
+    CharacterData,
+}
+
+/// A change of the DOM tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MutationEntry {
+    pub kind: MutationKind,
+    /// The name of the changed attribute.
+    pub attribute_name: Option<String>,
+    /// The previous value, recorded if `MutationOptions::old_value` is specified.
+    pub old_value: Option<String>,
+    pub added_nodes: u32,
+    pub removed_nodes: u32,
+}
+
+impl Subscription for MutationObserver {
+    type Msg = MutationEntry;
+    type Stream = MutationObserverStream;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        let target = env
+            .get_element_by_id(&self.target_id)
+            .ok_or_else(|| crate::Error::new(ErrorKind::MissingElement(self.target_id.clone())))?;
+
+        let (tx, rx) = mpsc::unbounded();
+
+        let cb = Closure::wrap(Box::new(move |records: js_sys::Array| {
+            for record in records.iter() {
+                let record: web::MutationRecord = record.unchecked_into();
+                let kind = match &*record.type_() {
+                    "attributes" => MutationKind::Attributes,
+                    "characterData" => MutationKind::CharacterData,
+                    _ => MutationKind::ChildList,
+                };
+                tx.unbounded_send(MutationEntry {
+                    kind,
+                    attribute_name: record.attribute_name(),
+                    old_value: record.old_value(),
+                    added_nodes: record.added_nodes().length(),
+                    removed_nodes: record.removed_nodes().length(),
+                })
+                .unwrap_throw();
+            }
+        }) as Box<dyn FnMut(js_sys::Array)>);
+
+        let options = &self.options;
+        let init = web::MutationObserverInit::new();
+        init.set_child_list(options.child_list);
+        init.set_attributes(options.attributes);
+        init.set_character_data(options.character_data);
+        init.set_subtree(options.subtree);
+        if options.old_value {
+            if options.attributes {
+                init.set_attribute_old_value(true);
+            }
+            if options.character_data {
+                init.set_character_data_old_value(true);
+            }
+        }
+        if !options.attribute_filter.is_empty() {
+            let filter: js_sys::Array = options
+                .attribute_filter
+                .iter()
+                .map(|name| JsValue::from_str(name))
+                .collect();
+            init.set_attribute_filter(&filter);
+        }
+
+        let observer = web::MutationObserver::new(cb.as_ref().unchecked_ref())
+            .map_err(crate::Error::dom("new MutationObserver"))?;
+        observer
+            .observe_with_options(&target, &init)
+            .map_err(crate::Error::dom("MutationObserver.observe"))?;
+
+        Ok(MutationObserverStream {
+            rx,
+            observer,
+            _cb: cb,
+        })
+    }
+}
+
+pub struct MutationObserverStream {
+    rx: mpsc::UnboundedReceiver<MutationEntry>,
+    observer: web::MutationObserver,
+    _cb: Closure<dyn FnMut(js_sys::Array)>,
+}
+
+impl Drop for MutationObserverStream {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+impl Stream for MutationObserverStream {
+    type Item = MutationEntry;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().rx.poll_next_unpin(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rx.size_hint()
+    }
+}
+
+impl FusedStream for MutationObserverStream {
+    fn is_terminated(&self) -> bool {
+        self.rx.is_terminated()
+    }
+}
@@ -0,0 +1,27 @@
+use super::{state_stream::StateStream, Subscription};
+use crate::env::Env;
+
+/// Create a subscription that emits whether the browser is online on
+/// subscribe and whenever it is changed.
+#[inline]
+pub fn online_status() -> OnlineStatus {
+    OnlineStatus { _p: () }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct OnlineStatus {
+    _p: (),
+}
+
+impl Subscription for OnlineStatus {
+    type Msg = bool;
+    type Stream = StateStream<bool>;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        let reader = env.clone();
+        Ok(StateStream::new(
+            &[(&env.window, "online"), (&env.window, "offline")],
+            move || reader.is_online(),
+        ))
+    }
+}
@@ -0,0 +1,68 @@
+use super::{event_stream::EventStream, Subscription};
+use crate::env::Env;
+use serde::Deserialize;
+use std::{borrow::Cow, marker::PhantomData};
+use wasm_bindgen::JsCast as _;
+
+/// Create a subscription that emits the `click` events targeting outside of
+/// the element with the specified id, such as for closing popups.
+///
+/// The element is looked up at every event, so the subscription may be
+/// started before the element is rendered.  No events are emitted while the
+/// element does not exist.
+pub fn outside_click<T>(target_id: impl Into<Cow<'static, str>>) -> OutsideClick<T>
+where
+    T: for<'de> Deserialize<'de> + 'static,
+{
+    OutsideClick {
+        target_id: target_id.into(),
+        _marker: PhantomData,
+    }
+}
+
+pub struct OutsideClick<T> {
+    target_id: Cow<'static, str>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for OutsideClick<T> {
+    fn clone(&self) -> Self {
+        Self {
+            target_id: self.target_id.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> PartialEq for OutsideClick<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.target_id == other.target_id
+    }
+}
+
+impl<T> Subscription for OutsideClick<T>
+where
+    T: for<'de> Deserialize<'de> + 'static,
+{
+    type Msg = T;
+    type Stream = EventStream<T>;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        let env2 = env.clone();
+        let target_id = self.target_id;
+        Ok(EventStream::new(
+            &env.document,
+            "click".into(),
+            move |event| {
+                let element = match env2.get_element_by_id(&target_id) {
+                    Some(element) => element,
+                    None => return false,
+                };
+                // The target is retargeted to the host if the click is in a
+                // shadow root, so the original one is taken from the path.
+                let target = event.composed_path().get(0).dyn_into::<web::Node>().ok();
+                !element.contains(target.as_ref())
+            },
+        ))
+    }
+}
@@ -0,0 +1,98 @@
+use super::Subscription;
+use crate::{env::Env, error::ErrorKind};
+use futures::prelude::*;
+use futures::{
+    channel::mpsc,
+    stream::{FusedStream, Stream},
+    task::{self, Poll},
+};
+use std::{borrow::Cow, pin::Pin};
+use wasm_bindgen::prelude::*;
+
+/// Create a subscription that observes the size of the element with the
+/// specified id by using `ResizeObserver`.
+///
+/// The size of the element is emitted when observation is started, and
+/// then whenever it is changed.
+pub fn resize_observer(target_id: impl Into<Cow<'static, str>>) -> ResizeObserver {
+    ResizeObserver {
+        target_id: target_id.into(),
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct ResizeObserver {
+    target_id: Cow<'static, str>,
+}
+
+/// The size of the content box of the observed element.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ResizeEntry {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Subscription for ResizeObserver {
+    type Msg = ResizeEntry;
+    type Stream = ResizeObserverStream;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        let target = env
+            .get_element_by_id(&self.target_id)
+            .ok_or_else(|| crate::Error::new(ErrorKind::MissingElement(self.target_id.clone())))?;
+
+        let (tx, rx) = mpsc::unbounded();
+
+        let cb = Closure::wrap(Box::new(move |entries: js_sys::Array| {
+            for entry in entries.iter() {
+                let entry: web::ResizeObserverEntry = entry.unchecked_into();
+                let rect = entry.content_rect();
+                tx.unbounded_send(ResizeEntry {
+                    width: rect.width(),
+                    height: rect.height(),
+                })
+                .unwrap_throw();
+            }
+        }) as Box<dyn FnMut(js_sys::Array)>);
+
+        let observer = web::ResizeObserver::new(cb.as_ref().unchecked_ref())
+            .map_err(crate::Error::dom("new ResizeObserver"))?;
+        observer.observe(&target);
+
+        Ok(ResizeObserverStream {
+            rx,
+            observer,
+            _cb: cb,
+        })
+    }
+}
+
+pub struct ResizeObserverStream {
+    rx: mpsc::UnboundedReceiver<ResizeEntry>,
+    observer: web::ResizeObserver,
+    _cb: Closure<dyn FnMut(js_sys::Array)>,
+}
+
+impl Drop for ResizeObserverStream {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+impl Stream for ResizeObserverStream {
+    type Item = ResizeEntry;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().rx.poll_next_unpin(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rx.size_hint()
+    }
+}
+
+impl FusedStream for ResizeObserverStream {
+    fn is_terminated(&self) -> bool {
+        self.rx.is_terminated()
+    }
+}
@@ -0,0 +1,93 @@
+use super::{url_changes, Subscription, UrlChangesStream};
+use crate::env::Env;
+use futures::prelude::*;
+use futures::{
+    stream::{FusedStream, Stream},
+    task::{self, Poll},
+};
+use siro::router::{Route, Router};
+use std::{marker::PhantomData, pin::Pin};
+
+/// Create a subscription that emits the route when the URL is changed.
+///
+/// The changes are observed in the same way as `url_changes`.
+/// The emitted value is `None` if the new URL does not match any routes.
+pub fn route_changes<R>(router: Router) -> RouteChanges<R>
+where
+    R: Route + 'static,
+{
+    RouteChanges {
+        router,
+        _marker: PhantomData,
+    }
+}
+
+pub struct RouteChanges<R> {
+    router: Router,
+    _marker: PhantomData<fn() -> R>,
+}
+
+impl<R> Clone for RouteChanges<R> {
+    fn clone(&self) -> Self {
+        Self {
+            router: self.router,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<R> PartialEq for RouteChanges<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.router == other.router
+    }
+}
+
+impl<R> Subscription for RouteChanges<R>
+where
+    R: Route + 'static,
+{
+    type Msg = Option<R>;
+    type Stream = RouteChangesStream<R>;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        Ok(RouteChangesStream {
+            urls: url_changes().subscribe(env)?,
+            router: self.router,
+            _marker: PhantomData,
+        })
+    }
+}
+
+pub struct RouteChangesStream<R> {
+    urls: UrlChangesStream,
+    router: Router,
+    _marker: PhantomData<fn() -> R>,
+}
+
+impl<R> Stream for RouteChangesStream<R>
+where
+    R: Route,
+{
+    type Item = Option<R>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.get_mut();
+        let router = me.router;
+        me.urls
+            .poll_next_unpin(cx)
+            .map(|url| url.map(|url| router.parse(&url)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.urls.size_hint()
+    }
+}
+
+impl<R> FusedStream for RouteChangesStream<R>
+where
+    R: Route,
+{
+    fn is_terminated(&self) -> bool {
+        self.urls.is_terminated()
+    }
+}
@@ -0,0 +1,375 @@
+use super::Subscription;
+use crate::env::Env;
+use futures::prelude::*;
+use futures::{
+    channel::mpsc,
+    stream::{FusedStream, Stream},
+    task::{self, Poll},
+};
+use gloo_events::{EventListener, EventListenerOptions};
+use std::{borrow::Cow, pin::Pin};
+use wasm_bindgen::prelude::*;
+
+/// The maximum interval between the keys in a sequence, in milliseconds.
+const SEQUENCE_TIMEOUT: f64 = 1000.0;
+
+/// Create a subscription that emits the messages bound to keyboard shortcuts.
+///
+/// A shortcut is a chord such as `"ctrl+s"` or `"shift+?"`, or a sequence of
+/// chords separated by whitespace such as `"g i"`.  A chord consists of the
+/// modifiers `ctrl`, `alt`, `shift`, `meta` and `mod` joined with `+`,
+/// followed by the key name compared with `KeyboardEvent.key` ignoring case.
+/// `mod` means `meta` on Apple platforms and `ctrl` on the others.
+///
+/// When more than one shortcut matches the pressed keys, the one that comes
+/// first is used.  The key events from text inputs are ignored by default.
+/// The invalid shortcuts are reported as an error on subscribe.
+pub fn shortcuts<I, S, TMsg>(bindings: I) -> Shortcuts<TMsg>
+where
+    I: IntoIterator<Item = (S, TMsg)>,
+    S: Into<Cow<'static, str>>,
+    TMsg: Clone + 'static,
+{
+    Shortcuts {
+        bindings: bindings
+            .into_iter()
+            .map(|(shortcut, msg)| (shortcut.into(), msg))
+            .collect(),
+        prevent_default: false,
+        in_text_inputs: false,
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct Shortcuts<TMsg> {
+    bindings: Vec<(Cow<'static, str>, TMsg)>,
+    prevent_default: bool,
+    in_text_inputs: bool,
+}
+
+impl<TMsg> Shortcuts<TMsg> {
+    /// Cancel the default action of the browser for the matched shortcuts.
+    pub fn prevent_default(mut self) -> Self {
+        self.prevent_default = true;
+        self
+    }
+
+    /// Handle the key events from text inputs, such as `<input>`, `<textarea>`
+    /// and the elements with `contenteditable`.
+    pub fn in_text_inputs(mut self) -> Self {
+        self.in_text_inputs = true;
+        self
+    }
+}
+
+impl<TMsg> Subscription for Shortcuts<TMsg>
+where
+    TMsg: Clone + 'static,
+{
+    type Msg = TMsg;
+    type Stream = ShortcutsStream<TMsg>;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        let is_apple = env.window.navigator().platform().is_ok_and(|platform| {
+            ["Mac", "iPhone", "iPad"]
+                .iter()
+                .any(|name| platform.contains(name))
+        });
+
+        let bindings = self
+            .bindings
+            .into_iter()
+            .map(|(shortcut, msg)| Ok((parse_sequence(&shortcut, is_apple)?, msg)))
+            .collect::<crate::Result<Vec<_>>>()?;
+        let max_len = bindings.iter().map(|(seq, _)| seq.len()).max().unwrap_or(0);
+
+        let prevent_default = self.prevent_default;
+        let in_text_inputs = self.in_text_inputs;
+        let mut history: Vec<(Chord, f64)> = vec![];
+
+        let (tx, rx) = mpsc::unbounded();
+
+        let options = EventListenerOptions::enable_prevent_default();
+        let listener =
+            EventListener::new_with_options(&env.window, "keydown", options, move |event| {
+                let event = match event.dyn_ref::<web::KeyboardEvent>() {
+                    Some(event) => event,
+                    None => return,
+                };
+                if !in_text_inputs && is_text_input(event) {
+                    return;
+                }
+                let chord = match Chord::from_event(event) {
+                    Some(chord) => chord,
+                    None => return,
+                };
+
+                if max_len == 0 {
+                    return;
+                }
+
+                let now = js_sys::Date::now();
+                history.retain(|&(_, time)| now - time <= SEQUENCE_TIMEOUT);
+                if history.len() >= max_len {
+                    history.drain(..=history.len() - max_len);
+                }
+                history.push((chord, now));
+
+                let matched = bindings.iter().find(|(seq, _)| {
+                    seq.len() <= history.len()
+                        && seq
+                            .iter()
+                            .zip(&history[history.len() - seq.len()..])
+                            .all(|(expected, (actual, _))| expected.matches(actual))
+                });
+                if let Some((_, msg)) = matched {
+                    history.clear();
+                    if prevent_default {
+                        event.prevent_default();
+                    }
+                    tx.unbounded_send(msg.clone()).unwrap_throw();
+                }
+            });
+
+        Ok(ShortcutsStream {
+            rx,
+            _listener: listener,
+        })
+    }
+}
+
+pub struct ShortcutsStream<TMsg> {
+    rx: mpsc::UnboundedReceiver<TMsg>,
+    _listener: EventListener,
+}
+
+impl<TMsg> Stream for ShortcutsStream<TMsg> {
+    type Item = TMsg;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().rx.poll_next_unpin(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rx.size_hint()
+    }
+}
+
+impl<TMsg> FusedStream for ShortcutsStream<TMsg> {
+    fn is_terminated(&self) -> bool {
+        self.rx.is_terminated()
+    }
+}
+
+// ==== chords ====
+
+#[derive(Debug, Clone, PartialEq)]
+struct Chord {
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    meta: bool,
+    key: String,
+}
+
+impl Chord {
+    fn from_event(event: &web::KeyboardEvent) -> Option<Self> {
+        let key = event.key().to_lowercase();
+        // The modifier keys themselves do not form any chords.
+        if let "control" | "alt" | "shift" | "meta" = &*key {
+            return None;
+        }
+        Some(Self {
+            ctrl: event.ctrl_key(),
+            alt: event.alt_key(),
+            shift: event.shift_key(),
+            meta: event.meta_key(),
+            key,
+        })
+    }
+
+    fn matches(&self, actual: &Chord) -> bool {
+        // The symbols such as `?` are typed with Shift on most keyboard
+        // layouts, so Shift is not checked unless specified explicitly.
+        let is_symbol = {
+            let mut chars = self.key.chars();
+            matches!((chars.next(), chars.next()), (Some(c), None) if !c.is_alphanumeric() && c != ' ')
+        };
+        self.key == actual.key
+            && self.ctrl == actual.ctrl
+            && self.alt == actual.alt
+            && self.meta == actual.meta
+            && (self.shift == actual.shift || is_symbol && !self.shift)
+    }
+}
+
+fn parse_sequence(shortcut: &str, is_apple: bool) -> crate::Result<Vec<Chord>> {
+    let sequence = shortcut
+        .split_whitespace()
+        .map(|chord| parse_chord(chord, is_apple))
+        .collect::<crate::Result<Vec<_>>>()?;
+    if sequence.is_empty() {
+        return Err(crate::Error::custom("empty shortcut"));
+    }
+    Ok(sequence)
+}
+
+fn parse_chord(s: &str, is_apple: bool) -> crate::Result<Chord> {
+    let invalid = || crate::Error::custom(format!("invalid shortcut: {:?}", s));
+
+    // `+` itself may be the key, as in `ctrl++`.
+    let (modifiers, key) = match s.strip_suffix("++") {
+        Some(modifiers) => (Some(modifiers), "+"),
+        None if s == "+" => (None, "+"),
+        None => match s.rsplit_once('+') {
+            Some((modifiers, key)) => (Some(modifiers), key),
+            None => (None, s),
+        },
+    };
+    if key.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut chord = Chord {
+        ctrl: false,
+        alt: false,
+        shift: false,
+        meta: false,
+        key: normalize_key(&key.to_lowercase()),
+    };
+    // The empty modifiers, as in `+s` or `ctrl++s`, are rejected.
+    for modifier in modifiers
+        .into_iter()
+        .flat_map(|modifiers| modifiers.split('+'))
+    {
+        match &*modifier.to_lowercase() {
+            "ctrl" | "control" => chord.ctrl = true,
+            "alt" | "option" => chord.alt = true,
+            "shift" => chord.shift = true,
+            "meta" | "cmd" | "command" | "super" => chord.meta = true,
+            "mod" if is_apple => chord.meta = true,
+            "mod" => chord.ctrl = true,
+            _ => return Err(invalid()),
+        }
+    }
+
+    Ok(chord)
+}
+
+/// Convert the aliases of key names into the values of `KeyboardEvent.key`.
+fn normalize_key(key: &str) -> String {
+    match key {
+        "esc" => "escape",
+        "space" => " ",
+        "plus" => "+",
+        "return" => "enter",
+        "del" => "delete",
+        "up" => "arrowup",
+        "down" => "arrowdown",
+        "left" => "arrowleft",
+        "right" => "arrowright",
+        key => key,
+    }
+    .to_owned()
+}
+
+fn is_text_input(event: &web::KeyboardEvent) -> bool {
+    let element = match event
+        .target()
+        .and_then(|target| target.dyn_into::<web::HtmlElement>().ok())
+    {
+        Some(element) => element,
+        None => return false,
+    };
+    matches!(&*element.tag_name(), "INPUT" | "TEXTAREA" | "SELECT") || element.is_content_editable()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord(ctrl: bool, alt: bool, shift: bool, meta: bool, key: &str) -> Chord {
+        Chord {
+            ctrl,
+            alt,
+            shift,
+            meta,
+            key: key.into(),
+        }
+    }
+
+    #[test]
+    fn parse_modifier_aliases() {
+        let ctrl_s = chord(true, false, false, false, "s");
+        assert_eq!(parse_chord("ctrl+s", false).unwrap(), ctrl_s);
+        assert_eq!(parse_chord("Control+S", false).unwrap(), ctrl_s);
+        assert_eq!(parse_chord("mod+s", false).unwrap(), ctrl_s);
+        assert_eq!(
+            parse_chord("mod+s", true).unwrap(),
+            chord(false, false, false, true, "s")
+        );
+        assert_eq!(
+            parse_chord("CMD+Option+Shift+Esc", false).unwrap(),
+            chord(false, true, true, true, "escape")
+        );
+    }
+
+    #[test]
+    fn parse_plus_as_key() {
+        assert_eq!(
+            parse_chord("+", false).unwrap(),
+            chord(false, false, false, false, "+")
+        );
+        assert_eq!(
+            parse_chord("ctrl++", false).unwrap(),
+            chord(true, false, false, false, "+")
+        );
+        assert_eq!(
+            parse_chord("ctrl+plus", false).unwrap(),
+            chord(true, false, false, false, "+")
+        );
+    }
+
+    #[test]
+    fn parse_invalid_chords() {
+        for s in &["ctrl+", "+s", "++", "ctrl++s", "hyper+s"] {
+            assert!(parse_chord(s, false).is_err(), "{:?} must be rejected", s);
+        }
+    }
+
+    #[test]
+    fn parse_sequences() {
+        assert_eq!(
+            parse_sequence("g  i", false).unwrap(),
+            [
+                chord(false, false, false, false, "g"),
+                chord(false, false, false, false, "i"),
+            ]
+        );
+        assert!(parse_sequence("", false).is_err());
+        assert!(parse_sequence("g ctrl+", false).is_err());
+    }
+
+    #[test]
+    fn match_case_and_modifiers() {
+        let ctrl_s = parse_chord("ctrl+S", false).unwrap();
+        assert!(ctrl_s.matches(&chord(true, false, false, false, "s")));
+        assert!(!ctrl_s.matches(&chord(false, false, false, false, "s")));
+        assert!(!ctrl_s.matches(&chord(true, true, false, false, "s")));
+        assert!(!ctrl_s.matches(&chord(true, false, true, false, "s")));
+    }
+
+    #[test]
+    fn match_symbols_regardless_of_shift() {
+        let question = parse_chord("?", false).unwrap();
+        assert!(question.matches(&chord(false, false, true, false, "?")));
+        assert!(question.matches(&chord(false, false, false, false, "?")));
+
+        let shift_question = parse_chord("shift+?", false).unwrap();
+        assert!(!shift_question.matches(&chord(false, false, false, false, "?")));
+
+        // Shift is checked for the letters and the named keys.
+        let space = parse_chord("space", false).unwrap();
+        assert!(!space.matches(&chord(false, false, true, false, " ")));
+    }
+}
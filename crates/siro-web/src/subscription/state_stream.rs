@@ -0,0 +1,75 @@
+use futures::prelude::*;
+use futures::{
+    channel::mpsc,
+    stream::{FusedStream, Stream},
+    task::{self, Poll},
+};
+use gloo_events::EventListener;
+use std::{cell::RefCell, pin::Pin, rc::Rc};
+use wasm_bindgen::prelude::*;
+
+/// The stream of a state value that is re-read when the events are fired.
+///
+/// The current value is emitted first, and then the value is emitted only
+/// when it differs from the previous one.
+pub struct StateStream<T> {
+    rx: mpsc::UnboundedReceiver<T>,
+    _listeners: Vec<EventListener>,
+}
+
+impl<T> StateStream<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    pub(super) fn new<F>(targets: &[(&web::EventTarget, &'static str)], read: F) -> Self
+    where
+        F: Fn() -> T + 'static,
+    {
+        let (tx, rx) = mpsc::unbounded();
+
+        let current = read();
+        tx.unbounded_send(current.clone()).unwrap_throw();
+
+        let read = Rc::new(read);
+        let last = Rc::new(RefCell::new(current));
+        let listeners = targets
+            .iter()
+            .map(|&(target, event_type)| {
+                let read = read.clone();
+                let last = last.clone();
+                let tx = tx.clone();
+                EventListener::new(target, event_type, move |_event| {
+                    let value = read();
+                    let mut last = last.borrow_mut();
+                    if *last != value {
+                        *last = value.clone();
+                        tx.unbounded_send(value).unwrap_throw();
+                    }
+                })
+            })
+            .collect();
+
+        Self {
+            rx,
+            _listeners: listeners,
+        }
+    }
+}
+
+impl<T> Stream for StateStream<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().rx.poll_next_unpin(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rx.size_hint()
+    }
+}
+
+impl<T> FusedStream for StateStream<T> {
+    fn is_terminated(&self) -> bool {
+        self.rx.is_terminated()
+    }
+}
@@ -0,0 +1,134 @@
+use super::Subscription;
+use crate::{
+    env::Env,
+    storage::{decode, strip_namespace, StorageArea},
+};
+use futures::prelude::*;
+use futures::{
+    channel::mpsc,
+    stream::{FusedStream, Stream},
+    task::{self, Poll},
+};
+use gloo_events::EventListener;
+use serde::de::DeserializeOwned;
+use std::{borrow::Cow, marker::PhantomData, pin::Pin};
+use wasm_bindgen::prelude::*;
+
+/// Create a subscription that emits the changes of the storage in the
+/// namespace, made by other documents such as the other tabs.
+///
+/// The changes made by the current document are not notified, according to
+/// the specification of the `storage` event.
+pub fn storage_changes<T>(
+    area: StorageArea,
+    namespace: impl Into<Cow<'static, str>>,
+) -> StorageChanges<T>
+where
+    T: DeserializeOwned + 'static,
+{
+    StorageChanges {
+        area,
+        namespace: namespace.into(),
+        _marker: PhantomData,
+    }
+}
+
+pub struct StorageChanges<T> {
+    area: StorageArea,
+    namespace: Cow<'static, str>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for StorageChanges<T> {
+    fn clone(&self) -> Self {
+        Self {
+            area: self.area,
+            namespace: self.namespace.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> PartialEq for StorageChanges<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.area == other.area && self.namespace == other.namespace
+    }
+}
+
+/// A change of the storage.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StorageChange<T> {
+    /// The changed key without the namespace, or `None` if the whole storage is cleared.
+    pub key: Option<String>,
+    /// The previous value, or `None` if it was not set or failed to decode.
+    pub old_value: Option<T>,
+    /// The new value, or `None` if it has been removed or failed to decode.
+    pub new_value: Option<T>,
+}
+
+impl<T> Subscription for StorageChanges<T>
+where
+    T: DeserializeOwned + 'static,
+{
+    type Msg = StorageChange<T>;
+    type Stream = StorageChangesStream<T>;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        let storage = self.area.storage(&env.window)?;
+        let namespace = self.namespace;
+
+        let (tx, rx) = mpsc::unbounded();
+        let listener = EventListener::new(&env.window, "storage", move |event| {
+            let event: &web::StorageEvent = event.unchecked_ref();
+
+            let area = event.storage_area();
+            if area.as_ref().map(AsRef::<JsValue>::as_ref) != Some(storage.as_ref()) {
+                return;
+            }
+
+            let key = match event.key() {
+                Some(key) => match strip_namespace(&namespace, &key) {
+                    Some(key) => Some(key.to_owned()),
+                    None => return,
+                },
+                None => None,
+            };
+
+            let decode_value = |value: Option<String>| value.and_then(|value| decode(&value).ok());
+            let change = StorageChange {
+                key,
+                old_value: decode_value(event.old_value()),
+                new_value: decode_value(event.new_value()),
+            };
+            tx.unbounded_send(change).unwrap_throw();
+        });
+
+        Ok(StorageChangesStream {
+            rx,
+            _listener: listener,
+        })
+    }
+}
+
+pub struct StorageChangesStream<T> {
+    rx: mpsc::UnboundedReceiver<StorageChange<T>>,
+    _listener: EventListener,
+}
+
+impl<T> Stream for StorageChangesStream<T> {
+    type Item = StorageChange<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().rx.poll_next_unpin(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rx.size_hint()
+    }
+}
+
+impl<T> FusedStream for StorageChangesStream<T> {
+    fn is_terminated(&self) -> bool {
+        self.rx.is_terminated()
+    }
+}
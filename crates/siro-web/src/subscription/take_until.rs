@@ -0,0 +1,92 @@
+use super::Subscription;
+use crate::env::Env;
+use futures::prelude::*;
+use futures::{
+    stream::{FusedStream, Stream},
+    task::{self, Poll},
+};
+use std::pin::Pin;
+
+#[derive(Clone, PartialEq)]
+pub struct TakeUntil<S, N> {
+    subscription: S,
+    notifier: N,
+}
+
+impl<S, N> TakeUntil<S, N> {
+    pub(super) fn new(subscription: S, notifier: N) -> Self {
+        Self {
+            subscription,
+            notifier,
+        }
+    }
+}
+
+impl<S, N> Subscription for TakeUntil<S, N>
+where
+    S: Subscription,
+    S::Stream: Unpin,
+    N: Subscription,
+    N::Stream: Unpin,
+{
+    type Msg = S::Msg;
+    type Stream = TakeUntilStream<S::Stream, N::Stream>;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        Ok(TakeUntilStream {
+            stream: self.subscription.subscribe(env)?,
+            notifier: Some(self.notifier.subscribe(env)?),
+            terminated: false,
+        })
+    }
+}
+
+pub struct TakeUntilStream<St, Nt> {
+    stream: St,
+    notifier: Option<Nt>,
+    terminated: bool,
+}
+
+impl<St, Nt> Stream for TakeUntilStream<St, Nt>
+where
+    St: Stream + Unpin,
+    Nt: FusedStream + Unpin,
+{
+    type Item = St::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.get_mut();
+        if me.terminated {
+            return Poll::Ready(None);
+        }
+
+        // The notifier is released as soon as it is finished without emitting
+        // any messages, and then the inner stream continues forever.
+        if let Some(ref mut notifier) = me.notifier {
+            match notifier.poll_next_unpin(cx) {
+                Poll::Ready(Some(..)) => {
+                    me.terminated = true;
+                    return Poll::Ready(None);
+                }
+                Poll::Ready(None) => me.notifier = None,
+                Poll::Pending => (),
+            }
+        }
+
+        let msg = futures::ready!(me.stream.poll_next_unpin(cx));
+        if msg.is_none() {
+            me.terminated = true;
+        }
+        Poll::Ready(msg)
+    }
+}
+
+impl<St, Nt> FusedStream for TakeUntilStream<St, Nt>
+where
+    St: Stream + Unpin,
+    Nt: FusedStream + Unpin,
+{
+    fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+}
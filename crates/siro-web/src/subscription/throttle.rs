@@ -0,0 +1,115 @@
+use super::{timeout::TimeoutStream, Subscription};
+use crate::env::Env;
+use futures::prelude::*;
+use futures::{
+    stream::{FusedStream, Stream},
+    task::{self, Poll},
+};
+use std::{pin::Pin, time::Duration};
+
+#[derive(Clone, PartialEq)]
+pub struct Throttle<S> {
+    subscription: S,
+    duration: Duration,
+}
+
+impl<S> Throttle<S> {
+    pub(super) fn new(subscription: S, duration: Duration) -> Self {
+        Self {
+            subscription,
+            duration,
+        }
+    }
+}
+
+impl<S> Subscription for Throttle<S>
+where
+    S: Subscription,
+    S::Stream: Unpin,
+    S::Msg: Unpin,
+{
+    type Msg = S::Msg;
+    type Stream = ThrottleStream<S::Stream>;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        Ok(ThrottleStream {
+            stream: self.subscription.subscribe(env)?,
+            window: env.window.clone(),
+            duration: self.duration,
+            pending: None,
+            timer: None,
+        })
+    }
+}
+
+pub struct ThrottleStream<St: Stream> {
+    stream: St,
+    window: web::Window,
+    duration: Duration,
+    pending: Option<St::Item>,
+    timer: Option<TimeoutStream>,
+}
+
+impl<St> ThrottleStream<St>
+where
+    St: Stream,
+{
+    fn start_timer(&mut self, cx: &mut task::Context<'_>) {
+        self.timer = TimeoutStream::new(&self.window, self.duration).ok();
+        if let Some(ref mut timer) = self.timer {
+            // Register the waker to the new timer.
+            let _ = timer.poll_next_unpin(cx);
+        }
+    }
+}
+
+impl<St> Stream for ThrottleStream<St>
+where
+    St: FusedStream + Unpin,
+    St::Item: Unpin,
+{
+    type Item = St::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.get_mut();
+
+        // At the end of the period, the latest message received during it is emitted.
+        if let Some(ref mut timer) = me.timer {
+            if timer.poll_next_unpin(cx).is_ready() {
+                me.timer = None;
+                if let Some(msg) = me.pending.take() {
+                    me.start_timer(cx);
+                    return Poll::Ready(Some(msg));
+                }
+            }
+        }
+
+        while !me.stream.is_terminated() {
+            match me.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(msg)) if me.timer.is_none() => {
+                    me.start_timer(cx);
+                    return Poll::Ready(Some(msg));
+                }
+                Poll::Ready(Some(msg)) => me.pending = Some(msg),
+                Poll::Ready(None) | Poll::Pending => break,
+            }
+        }
+
+        if me.stream.is_terminated() {
+            me.timer = None;
+            return Poll::Ready(me.pending.take());
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<St> FusedStream for ThrottleStream<St>
+where
+    St: FusedStream + Unpin,
+    St::Item: Unpin,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.pending.is_none()
+    }
+}
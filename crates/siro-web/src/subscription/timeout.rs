@@ -0,0 +1,101 @@
+use super::{duration_to_millis, Subscription};
+use crate::env::Env;
+use futures::prelude::*;
+use futures::{
+    channel::mpsc,
+    stream::{FusedStream, Stream},
+    task::{self, Poll},
+};
+use std::{pin::Pin, time::Duration};
+use wasm_bindgen::prelude::*;
+
+/// Create a subscription that emits a message once after the specified duration.
+///
+/// The stream is terminated after the message is emitted.
+#[inline]
+pub fn timeout(duration: Duration) -> Timeout {
+    Timeout { duration }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct Timeout {
+    duration: Duration,
+}
+
+impl Subscription for Timeout {
+    type Msg = ();
+    type Stream = TimeoutStream;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        TimeoutStream::new(&env.window, self.duration)
+    }
+}
+
+pub struct TimeoutStream {
+    rx: mpsc::UnboundedReceiver<()>,
+    inner: Option<Inner>,
+}
+
+struct Inner {
+    window: web::Window,
+    id: i32,
+    _cb: Closure<dyn FnMut()>,
+}
+
+impl TimeoutStream {
+    pub(super) fn new(window: &web::Window, duration: Duration) -> crate::Result<Self> {
+        let (tx, rx) = mpsc::unbounded();
+
+        // The sender is dropped after calling the callback, which terminates the stream.
+        let cb = Closure::once(move || {
+            let _ = tx.unbounded_send(());
+        });
+
+        let id = window
+            .set_timeout_with_callback_and_timeout_and_arguments_0(
+                cb.as_ref().unchecked_ref(),
+                duration_to_millis(duration),
+            )
+            .map_err(crate::Error::dom("setTimeout"))?;
+
+        Ok(Self {
+            rx,
+            inner: Some(Inner {
+                window: window.clone(),
+                id,
+                _cb: cb,
+            }),
+        })
+    }
+
+    fn unsubscribe(&mut self) -> Result<(), JsValue> {
+        if let Some(inner) = self.inner.take() {
+            inner.window.clear_timeout_with_handle(inner.id);
+        }
+        Ok(())
+    }
+}
+
+impl Drop for TimeoutStream {
+    fn drop(&mut self) {
+        let _ = self.unsubscribe();
+    }
+}
+
+impl Stream for TimeoutStream {
+    type Item = ();
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().rx.poll_next_unpin(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rx.size_hint()
+    }
+}
+
+impl FusedStream for TimeoutStream {
+    fn is_terminated(&self) -> bool {
+        self.rx.is_terminated()
+    }
+}
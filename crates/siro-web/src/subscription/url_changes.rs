@@ -0,0 +1,87 @@
+use super::Subscription;
+use crate::env::Env;
+use futures::prelude::*;
+use futures::{
+    channel::mpsc,
+    stream::{FusedStream, Stream},
+    task::{self, Poll},
+};
+use gloo_events::EventListener;
+use std::{cell::RefCell, pin::Pin, rc::Rc};
+use wasm_bindgen::prelude::*;
+
+/// The type of the event dispatched on `window` by `Navigation::push_url`
+/// and `replace_url`.
+pub(crate) const URL_CHANGE_EVENT: &str = "siro:urlchange";
+
+/// Create a subscription that emits the new URL when it is changed by
+/// `popstate` or `hashchange` events.
+///
+/// The URL changes caused by `Navigation::push_url` and `replace_url` are
+/// also notified.  The same URL is not emitted twice in a row.
+#[inline]
+pub fn url_changes() -> UrlChanges {
+    UrlChanges { _p: () }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct UrlChanges {
+    _p: (),
+}
+
+impl Subscription for UrlChanges {
+    type Msg = String;
+    type Stream = UrlChangesStream;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        let (tx, rx) = mpsc::unbounded();
+
+        let last_url = Rc::new(RefCell::new(env.current_url().unwrap_or_default()));
+
+        let listeners = ["popstate", "hashchange", URL_CHANGE_EVENT]
+            .iter()
+            .map(|&event_type| {
+                let window = env.window.clone();
+                let last_url = last_url.clone();
+                let tx = tx.clone();
+                EventListener::new(&env.window, event_type, move |_event| {
+                    if let Ok(url) = window.location().href() {
+                        let mut last_url = last_url.borrow_mut();
+                        if *last_url != url {
+                            *last_url = url.clone();
+                            tx.unbounded_send(url).unwrap_throw();
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        Ok(UrlChangesStream {
+            rx,
+            _listeners: listeners,
+        })
+    }
+}
+
+pub struct UrlChangesStream {
+    rx: mpsc::UnboundedReceiver<String>,
+    _listeners: Vec<EventListener>,
+}
+
+impl Stream for UrlChangesStream {
+    type Item = String;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().rx.poll_next_unpin(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rx.size_hint()
+    }
+}
+
+impl FusedStream for UrlChangesStream {
+    fn is_terminated(&self) -> bool {
+        self.rx.is_terminated()
+    }
+}
@@ -0,0 +1,27 @@
+use super::{state_stream::StateStream, Subscription};
+use crate::env::{Env, Visibility};
+
+/// Create a subscription that emits the visibility of the document on
+/// subscribe and whenever it is changed.
+#[inline]
+pub fn visibility_changes() -> VisibilityChanges {
+    VisibilityChanges { _p: () }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct VisibilityChanges {
+    _p: (),
+}
+
+impl Subscription for VisibilityChanges {
+    type Msg = Visibility;
+    type Stream = StateStream<Visibility>;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        let reader = env.clone();
+        Ok(StateStream::new(
+            &[(&env.document, "visibilitychange")],
+            move || reader.visibility(),
+        ))
+    }
+}
@@ -1,15 +1,7 @@
-use super::Subscription;
+use super::{event_stream::EventStream, Subscription};
 use crate::env::Env;
-use futures::prelude::*;
-use futures::{
-    channel::mpsc,
-    stream::{FusedStream, Stream},
-    task::{self, Poll},
-};
-use gloo_events::EventListener;
 use serde::Deserialize;
-use std::{borrow::Cow, marker::PhantomData, pin::Pin};
-use wasm_bindgen::prelude::*;
+use std::{borrow::Cow, marker::PhantomData};
 
 pub fn window_event<T>(event_type: impl Into<Cow<'static, str>>) -> WindowEvent<T>
 where
@@ -26,56 +18,29 @@ pub struct WindowEvent<T> {
     _marker: PhantomData<fn() -> T>,
 }
 
-impl<T> Subscription for WindowEvent<T>
-where
-    T: for<'de> Deserialize<'de> + 'static,
-{
-    type Msg = T;
-    type Stream = WindowEventStream<T>;
-
-    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
-        let (tx, rx) = mpsc::unbounded();
-
-        let listener = EventListener::new(&env.window, self.event_type, move |event| {
-            let event: &JsValue = event.as_ref();
-            let de = serde_wasm_bindgen::Deserializer::from(event.clone());
-            if let Ok(msg) = T::deserialize(de) {
-                tx.unbounded_send(msg).unwrap_throw();
-            }
-        });
-
-        Ok(WindowEventStream {
-            rx,
-            _listener: Some(listener),
-        })
+impl<T> Clone for WindowEvent<T> {
+    fn clone(&self) -> Self {
+        Self {
+            event_type: self.event_type.clone(),
+            _marker: PhantomData,
+        }
     }
 }
 
-pub struct WindowEventStream<T> {
-    rx: mpsc::UnboundedReceiver<T>,
-    _listener: Option<EventListener>,
-}
-
-impl<T> Stream for WindowEventStream<T>
-where
-    T: for<'de> Deserialize<'de>,
-{
-    type Item = T;
-
-    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
-        self.get_mut().rx.poll_next_unpin(cx)
-    }
-
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.rx.size_hint()
+impl<T> PartialEq for WindowEvent<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.event_type == other.event_type
     }
 }
 
-impl<T> FusedStream for WindowEventStream<T>
+impl<T> Subscription for WindowEvent<T>
 where
-    T: for<'de> Deserialize<'de>,
+    T: for<'de> Deserialize<'de> + 'static,
 {
-    fn is_terminated(&self) -> bool {
-        self.rx.is_terminated()
+    type Msg = T;
+    type Stream = EventStream<T>;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        Ok(EventStream::new(&env.window, self.event_type, |_| true))
     }
 }
@@ -0,0 +1,26 @@
+use super::{state_stream::StateStream, Subscription};
+use crate::env::{Env, Size};
+
+/// Create a subscription that emits the size of the viewport on subscribe
+/// and whenever it is resized.
+#[inline]
+pub fn window_size() -> WindowSize {
+    WindowSize { _p: () }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct WindowSize {
+    _p: (),
+}
+
+impl Subscription for WindowSize {
+    type Msg = Size;
+    type Stream = StateStream<Size>;
+
+    fn subscribe(self, env: &Env) -> crate::Result<Self::Stream> {
+        let reader = env.clone();
+        Ok(StateStream::new(&[(&env.window, "resize")], move || {
+            reader.window_size()
+        }))
+    }
+}
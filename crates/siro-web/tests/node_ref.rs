@@ -0,0 +1,58 @@
+#![cfg(target_arch = "wasm32")]
+
+use siro::{
+    html::{div, p, span},
+    vdom::{node_ref, NodeRef},
+};
+use siro_web::Env;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn create_container(id: &str) -> web::Element {
+    let document = web::window().unwrap().document().unwrap();
+    let container = document.create_element("div").unwrap();
+    container.set_id(id);
+    document.body().unwrap().append_child(&container).unwrap();
+    container
+}
+
+fn tag_name(node_ref: &NodeRef) -> Option<String> {
+    node_ref
+        .get::<web::Element>()
+        .map(|element| element.tag_name().to_lowercase())
+}
+
+#[wasm_bindgen_test]
+fn replace_tag_of_referred_element() {
+    let env = Env::new().unwrap();
+    create_container("node-ref-replace");
+    let mut app = env.mount::<()>("#node-ref-replace").unwrap();
+    let r = NodeRef::new();
+
+    app.render(div(node_ref(&r), ())).unwrap();
+    assert_eq!(tag_name(&r).as_deref(), Some("div"));
+
+    app.render(p(node_ref(&r), ())).unwrap();
+    assert_eq!(tag_name(&r).as_deref(), Some("p"));
+
+    app.render(()).unwrap();
+    assert_eq!(tag_name(&r), None);
+}
+
+#[wasm_bindgen_test]
+fn remove_trailing_element_after_moving_ref() {
+    let env = Env::new().unwrap();
+    let mountpoint = create_container("node-ref-trailing");
+    let mut app = env.mount::<()>("#node-ref-trailing").unwrap();
+    let r = NodeRef::new();
+
+    app.render((div((), ()), span(node_ref(&r), ()))).unwrap();
+    let old = r.get::<web::Element>().unwrap();
+
+    // The new `<span>` replaces the `<div>`, and the old one is removed after it.
+    app.render(span(node_ref(&r), ())).unwrap();
+    let new = r.get::<web::Element>().unwrap();
+    assert_ne!(new, old);
+    assert_eq!(mountpoint.first_element_child(), Some(new));
+}
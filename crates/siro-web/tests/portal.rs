@@ -0,0 +1,63 @@
+#![cfg(target_arch = "wasm32")]
+
+use siro::{
+    html::{div, p, span},
+    vdom::portal,
+};
+use siro_web::Env;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn create_container(id: &str) -> web::Element {
+    let document = web::window().unwrap().document().unwrap();
+    let container = document.create_element("div").unwrap();
+    container.set_id(id);
+    document.body().unwrap().append_child(&container).unwrap();
+    container
+}
+
+/// Return the names of the child nodes, e.g. `["#comment", "div"]`.
+fn child_names(node: &web::Element) -> Vec<String> {
+    let children = node.child_nodes();
+    (0..children.length())
+        .filter_map(|i| children.item(i))
+        .map(|child| child.node_name().to_lowercase())
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn replace_portal_with_element() {
+    let env = Env::new().unwrap();
+    let mountpoint = create_container("portal-to-element");
+    let target = create_container("portal-to-element-target");
+    let mut app = env.mount::<()>("#portal-to-element").unwrap();
+
+    app.render((
+        portal("#portal-to-element-target", span((), ())),
+        div((), ()),
+    ))
+    .unwrap();
+    assert_eq!(child_names(&mountpoint), ["#comment", "div"]);
+    assert_eq!(child_names(&target), ["span"]);
+
+    app.render((p((), ()), div((), ()))).unwrap();
+    assert_eq!(child_names(&mountpoint), ["p", "div"]);
+    assert!(child_names(&target).is_empty());
+}
+
+#[wasm_bindgen_test]
+fn replace_element_with_portal() {
+    let env = Env::new().unwrap();
+    let mountpoint = create_container("element-to-portal");
+    let target = create_container("element-to-portal-target");
+    let mut app = env.mount::<()>("#element-to-portal").unwrap();
+
+    app.render((div((), ()), p((), ()))).unwrap();
+    assert_eq!(child_names(&mountpoint), ["div", "p"]);
+
+    app.render((portal("#element-to-portal-target", span((), ())), p((), ())))
+        .unwrap();
+    assert_eq!(child_names(&mountpoint), ["#comment", "p"]);
+    assert_eq!(child_names(&target), ["span"]);
+}
@@ -0,0 +1,73 @@
+#![cfg(target_arch = "wasm32")]
+
+use siro::html::{attr, div, input};
+use siro_web::{Env, ErrorKind, ShadowMode};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn create_container(id: &str) -> web::Element {
+    let document = web::window().unwrap().document().unwrap();
+    let container = document.create_element("div").unwrap();
+    container.set_id(id);
+    document.body().unwrap().append_child(&container).unwrap();
+    container
+}
+
+#[wasm_bindgen_test]
+fn reuse_shadow_root_of_same_mode() {
+    let env = Env::new().unwrap();
+    create_container("shadow-open");
+    create_container("shadow-closed");
+
+    for (selector, mode) in &[
+        ("#shadow-open", ShadowMode::Open),
+        ("#shadow-closed", ShadowMode::Closed),
+    ] {
+        let mut app = env.mount_shadow::<()>(selector, *mode).unwrap();
+        app.render(div((), ())).unwrap();
+        drop(app);
+
+        env.mount_shadow::<()>(selector, *mode).unwrap();
+    }
+}
+
+#[wasm_bindgen_test]
+fn reject_shadow_root_of_other_mode() {
+    let env = Env::new().unwrap();
+    create_container("shadow-open-then-closed");
+    create_container("shadow-closed-then-open");
+
+    drop(env.mount_shadow::<()>("#shadow-open-then-closed", ShadowMode::Open));
+    let err = env
+        .mount_shadow::<()>("#shadow-open-then-closed", ShadowMode::Closed)
+        .err()
+        .unwrap();
+    assert_eq!(
+        *err.kind(),
+        ErrorKind::ShadowModeMismatch(ShadowMode::Closed)
+    );
+
+    drop(env.mount_shadow::<()>("#shadow-closed-then-open", ShadowMode::Closed));
+    let err = env
+        .mount_shadow::<()>("#shadow-closed-then-open", ShadowMode::Open)
+        .err()
+        .unwrap();
+    assert_eq!(*err.kind(), ErrorKind::ShadowModeMismatch(ShadowMode::Open));
+}
+
+#[wasm_bindgen_test]
+fn focus_element_in_shadow_root() {
+    let env = Env::new().unwrap();
+    let host = create_container("shadow-focus");
+    let mut app = env
+        .mount_shadow::<()>("#shadow-focus", ShadowMode::Open)
+        .unwrap();
+
+    app.render(input::text(attr::id("shadow-focus-input")))
+        .unwrap();
+    app.focus("shadow-focus-input").unwrap();
+
+    let active = host.shadow_root().unwrap().active_element().unwrap();
+    assert_eq!(active.id(), "shadow-focus-input");
+}
@@ -1,10 +1,13 @@
 use siro::prelude::*;
-use siro::{svg, vdom::style};
-use siro_web::subscription::{animation_frames, Subscription as _};
-
-use futures::prelude::*;
-use futures::select;
-use std::f32;
+use siro::{
+    effects::AllEffects,
+    svg,
+    vdom::{style, NodesRenderer},
+    Cmd, Program,
+};
+use siro_web::subscription::{every_at_wall_clock, Every, Map, Subscription as _};
+
+use std::{f32, time::Duration};
 use wasm_bindgen::prelude::*;
 use wee_alloc::WeeAlloc;
 
@@ -101,42 +104,52 @@ fn view_hand(stroke: &'static str, width: i32, length: f32, turns: f32) -> impl
     )
 }
 
-// ==== runtime ====
-
-#[wasm_bindgen(start)]
-pub async fn main() -> siro_web::Result<()> {
-    console_error_panic_hook::set_once();
+// ==== program ====
 
-    let env = siro_web::Env::new()?;
+struct Clock;
 
-    let mut app = env.mount("#app")?;
+impl Program for Clock {
+    type Model = Model;
+    type Msg = Msg;
+    type Subscriptions = Map<Every, fn(f64) -> Msg>;
 
-    let mut frames = env.subscribe(
-        animation_frames() //
-            .map(|_timestamp| Msg::Tick(current_time())),
-    )?;
+    fn init(&self) -> Model {
+        Model {
+            current: current_time(js_sys::Date::now()),
+        }
+    }
 
-    let mut model = Model {
-        current: current_time(),
-    };
-    app.render(view(&model))?;
+    fn update<E>(&self, model: &mut Model, msg: Msg, _: &mut E) -> Cmd<Msg>
+    where
+        E: AllEffects<Msg>,
+    {
+        update(model, msg);
+        Cmd::none()
+    }
 
-    loop {
-        let msg = select! {
-            msg = app.select_next_some() => msg,
-            msg = frames.select_next_some() => msg,
-            complete => break,
-        };
+    fn view<R>(&self, model: &Model, renderer: R) -> Result<R::Ok, R::Error>
+    where
+        R: NodesRenderer<Msg = Msg>,
+    {
+        view(model).render_nodes(renderer)
+    }
 
-        update(&mut model, msg);
-        app.render(view(&model))?;
+    fn subscriptions(&self, _: &Model) -> Self::Subscriptions {
+        every_at_wall_clock(Duration::from_secs(1))
+            .map(|timestamp| Msg::Tick(current_time(timestamp)))
     }
+}
+
+// ==== runtime ====
 
-    Ok(())
+#[wasm_bindgen(start)]
+pub async fn main() -> siro_web::Result<()> {
+    console_error_panic_hook::set_once();
+    siro_web::run(Clock, "#app").await
 }
 
-fn current_time() -> Time {
-    let date = js_sys::Date::new_0();
+fn current_time(timestamp: f64) -> Time {
+    let date = js_sys::Date::new(&timestamp.into());
     Time {
         hours: date.get_hours(),
         minutes: date.get_minutes(),
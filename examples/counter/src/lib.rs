@@ -1,5 +1,7 @@
 use siro::html::{button, div, event::on_click};
 use siro::prelude::*;
+use siro::{effects::AllEffects, vdom::NodesRenderer, Cmd, Program};
+use siro_web::subscription::{empty, Empty};
 
 use wasm_bindgen::prelude::*;
 use wee_alloc::WeeAlloc;
@@ -47,23 +49,43 @@ fn view(model: &Model) -> impl Nodes<Msg> {
     )
 }
 
-// ==== runtime ====
+// ==== program ====
 
-#[wasm_bindgen(start)]
-pub async fn main() -> siro_web::Result<()> {
-    console_error_panic_hook::set_once();
+struct Counter;
 
-    let env = siro_web::Env::new()?;
+impl Program for Counter {
+    type Model = Model;
+    type Msg = Msg;
+    type Subscriptions = Empty<Msg>;
 
-    let mut app = env.mount("#app")?;
+    fn init(&self) -> Model {
+        Model { value: 0 }
+    }
+
+    fn update<E>(&self, model: &mut Model, msg: Msg, _: &mut E) -> Cmd<Msg>
+    where
+        E: AllEffects<Msg>,
+    {
+        update(model, msg);
+        Cmd::none()
+    }
 
-    let mut model = Model { value: 0 };
-    app.render(view(&model))?;
+    fn view<R>(&self, model: &Model, renderer: R) -> Result<R::Ok, R::Error>
+    where
+        R: NodesRenderer<Msg = Msg>,
+    {
+        view(model).render_nodes(renderer)
+    }
 
-    while let Some(msg) = app.next_message().await {
-        update(&mut model, msg);
-        app.render(view(&model))?;
+    fn subscriptions(&self, _: &Model) -> Self::Subscriptions {
+        empty()
     }
+}
+
+// ==== runtime ====
 
-    Ok(())
+#[wasm_bindgen(start)]
+pub async fn main() -> siro_web::Result<()> {
+    console_error_panic_hook::set_once();
+    siro_web::run(Counter, "#app").await
 }
@@ -5,9 +5,9 @@ use siro::html::{
     input,
 };
 use siro::prelude::*;
+use siro::{effects::AllEffects, vdom::NodesRenderer, Cmd, Program};
+use siro_web::subscription::{empty, Empty};
 
-use futures::prelude::*;
-use futures::{select, stream::FuturesUnordered};
 use serde::Deserialize;
 use wasm_bindgen::prelude::*;
 use wee_alloc::WeeAlloc;
@@ -56,25 +56,16 @@ enum Msg {
     UpdateResponse(Branch),
 }
 
-// Cmd is a user-defined type that indicates the operations performed
-// by the runtime.
-//
-// Unlike Elm, this type is defined on the application side that is not
-// tied to the concrete logic.
-enum Cmd {
-    Fetch(String),
-}
-
-fn update(model: &mut Model, msg: Msg) -> Option<Cmd> {
+fn update(client: &reqwest::Client, model: &mut Model, msg: Msg) -> Cmd<Msg> {
     match msg {
         Msg::UpdateRepoSlug(slug) => {
             model.repo_slug = slug;
-            None
+            Cmd::none()
         }
 
         Msg::UpdateBranch(branch) => {
             model.branch = branch;
-            None
+            Cmd::none()
         }
 
         Msg::RequestFetch => {
@@ -87,12 +78,24 @@ fn update(model: &mut Model, msg: Msg) -> Option<Cmd> {
                 "https://api.github.com/repos/{}/branches/{}",
                 repo_slug, branch
             );
-            Some(Cmd::Fetch(url))
+
+            // The request is returned to the runtime as a command,
+            // and its result will be sent back as the value of `Msg`.
+            let send = client
+                .get(&url)
+                .header("Accept", "application/vnd.github.v3+json")
+                .send();
+            Cmd::perform(async move {
+                let resp = send.await.unwrap();
+                let text = resp.text().await.unwrap();
+                let branch_info: Branch = serde_json::from_str(&text).unwrap();
+                Msg::UpdateResponse(branch_info)
+            })
         }
 
         Msg::UpdateResponse(response) => {
             model.response.replace(response);
-            None
+            Cmd::none()
         }
     }
 }
@@ -130,66 +133,54 @@ fn view(model: &Model) -> impl Nodes<Msg> {
     )
 }
 
-// ==== runtime ====
+// ==== program ====
 
-#[wasm_bindgen(start)]
-pub async fn main() -> siro_web::Result<()> {
-    console_error_panic_hook::set_once();
+struct Fetch {
+    client: reqwest::Client,
+}
 
-    let env = siro_web::Env::new()?;
+impl Program for Fetch {
+    type Model = Model;
+    type Msg = Msg;
+    type Subscriptions = Empty<Msg>;
 
-    // Start the Web application and mount on the specified DOM node.
-    let mut app = env.mount("#app")?;
+    fn init(&self) -> Model {
+        Model {
+            repo_slug: "ubnt-intrepid/siro".into(),
+            branch: "main".into(),
+            response: None,
+        }
+    }
 
-    // Miscellaneous values used in event loop.
-    let client = reqwest::Client::new();
-    let mut cmd_tasks = FuturesUnordered::new();
+    fn update<E>(&self, model: &mut Model, msg: Msg, _: &mut E) -> Cmd<Msg>
+    where
+        E: AllEffects<Msg>,
+    {
+        update(&self.client, model, msg)
+    }
 
-    // Initialize the model and render as DOM tree.
-    let mut model = Model {
-        repo_slug: "ubnt-intrepid/siro".into(),
-        branch: "main".into(),
-        response: None,
-    };
-    app.render(view(&model))?;
-
-    // Run the event loop.
-    loop {
-        // Receive a message from the environment.
-        let msg = select! {
-            msg = app.select_next_some() => msg,
-            msg = cmd_tasks.select_next_some() => msg,
-            complete => break,
-        };
-
-        // Apply the message to model and update the view.
-        let cmd = update(&mut model, msg);
-        app.render(view(&model))?;
-
-        // Dispatch the command returned from application.
-        //
-        // Here, `cmd` is just a value that is not abstracted like `Cmd<Msg>`,
-        // and it is manually dispatched inside the event loop.
-        //
-        // The dispatch result will be returned to the application side
-        // as the value of `Msg`.
-        match cmd {
-            Some(Cmd::Fetch(url)) => {
-                let send = client
-                    .get(&url)
-                    .header("Accept", "application/vnd.github.v3+json")
-                    .send();
-                cmd_tasks.push(async move {
-                    let resp = send.await.unwrap();
-                    let text = resp.text().await.unwrap();
-                    let branch_info: Branch = serde_json::from_str(&text).unwrap();
-                    Msg::UpdateResponse(branch_info)
-                });
-            }
+    fn view<R>(&self, model: &Model, renderer: R) -> Result<R::Ok, R::Error>
+    where
+        R: NodesRenderer<Msg = Msg>,
+    {
+        view(model).render_nodes(renderer)
+    }
 
-            None => (),
-        }
+    fn subscriptions(&self, _: &Model) -> Self::Subscriptions {
+        empty()
     }
+}
 
-    Ok(())
+// ==== runtime ====
+
+#[wasm_bindgen(start)]
+pub async fn main() -> siro_web::Result<()> {
+    console_error_panic_hook::set_once();
+
+    let program = Fetch {
+        client: reqwest::Client::new(),
+    };
+
+    // Start the Web application and mount on the specified DOM node.
+    siro_web::run(program, "#app").await
 }
@@ -1,9 +1,9 @@
 use siro::prelude::*;
-use siro_web::subscription::{window_event, Subscription as _};
+use siro::{effects::AllEffects, vdom::NodesRenderer, Cmd, Program};
+use siro_web::subscription::{window_event, Map, Subscription as _, Throttle, WindowEvent};
 
-use futures::prelude::*;
-use futures::select;
 use serde::Deserialize;
+use std::time::Duration;
 use wasm_bindgen::prelude::*;
 use wee_alloc::WeeAlloc;
 
@@ -82,52 +82,65 @@ fn view(model: &Model) -> impl Nodes<Msg> {
     )
 }
 
-// ==== runtime ====
+// ==== program ====
 
-#[wasm_bindgen(start)]
-pub async fn main() -> siro_web::Result<()> {
-    console_error_panic_hook::set_once();
+struct Mouse;
+
+type MouseSubscription<S> = Map<S, fn(MouseEvent) -> Msg>;
 
-    let env = siro_web::Env::new()?;
+impl Program for Mouse {
+    type Model = Model;
+    type Msg = Msg;
+    type Subscriptions = (
+        MouseSubscription<WindowEvent<MouseEvent>>,
+        MouseSubscription<Throttle<WindowEvent<MouseEvent>>>,
+        MouseSubscription<WindowEvent<MouseEvent>>,
+    );
 
-    let mut app = env.mount("#app")?;
+    fn init(&self) -> Model {
+        Model::default()
+    }
+
+    fn update<E>(&self, model: &mut Model, msg: Msg, _: &mut E) -> Cmd<Msg>
+    where
+        E: AllEffects<Msg>,
+    {
+        update(model, msg);
+        Cmd::none()
+    }
+
+    fn view<R>(&self, model: &Model, renderer: R) -> Result<R::Ok, R::Error>
+    where
+        R: NodesRenderer<Msg = Msg>,
+    {
+        view(model).render_nodes(renderer)
+    }
 
-    let mut mousedown = env.subscribe(
-        window_event("mousedown") //
-            .map(|event| Msg {
+    fn subscriptions(&self, _: &Model) -> Self::Subscriptions {
+        (
+            window_event("mousedown").map(|event| Msg {
                 event,
                 button: Some(Button::Down),
             }),
-    )?;
-    let mut mousemove = env.subscribe(
-        window_event("mousemove") //
-            .map(|event| Msg {
-                event,
-                button: None,
-            }),
-    )?;
-    let mut mouseup = env.subscribe(
-        window_event("mouseup") //
-            .map(|event| Msg {
+            // The mouse moves are delivered at most once per frame.
+            window_event("mousemove")
+                .throttle(Duration::from_millis(16))
+                .map(|event| Msg {
+                    event,
+                    button: None,
+                }),
+            window_event("mouseup").map(|event| Msg {
                 event,
                 button: Some(Button::Up),
             }),
-    )?;
-
-    let mut model = Model::default();
-    app.render(view(&model))?;
-
-    loop {
-        let msg = select! {
-            msg = app.select_next_some() => msg,
-            msg = mouseup.select_next_some() => msg,
-            msg = mousedown.select_next_some() => msg,
-            msg = mousemove.select_next_some() => msg,
-            complete => break,
-        };
-        update(&mut model, msg);
-        app.render(view(&model))?;
+        )
     }
+}
 
-    Ok(())
+// ==== runtime ====
+
+#[wasm_bindgen(start)]
+pub async fn main() -> siro_web::Result<()> {
+    console_error_panic_hook::set_once();
+    siro_web::run(Mouse, "#app").await
 }
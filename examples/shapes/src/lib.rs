@@ -12,83 +12,150 @@ pub fn main() -> siro_web::Result<()> {
 
     let mut app = env.mount::<()>("#app")?;
 
-    app.render({
-        use siro::svg::{self, attr, svg};
+    app.render((svg_shapes(), canvas_shapes()))?;
 
-        svg(
-            (
-                attr::view_box("0 0 400 400"),
-                attr::width("400"),
-                attr::height("400"),
-            ),
-            (
-                svg::circle(
-                    (
-                        attr::cx("50"),
-                        attr::cy("50"),
-                        attr::r("40"),
-                        attr::fill("red"),
-                        attr::stroke("black"),
-                        attr::stroke_width("3"),
-                    ),
-                    (),
+    Ok(())
+}
+
+fn svg_shapes() -> impl siro::vdom::Nodes<()> {
+    use siro::svg::{self, attr, svg};
+
+    svg(
+        (
+            attr::view_box("0 0 400 400"),
+            attr::width("400"),
+            attr::height("400"),
+        ),
+        (
+            svg::circle(
+                (
+                    attr::cx("50"),
+                    attr::cy("50"),
+                    attr::r("40"),
+                    attr::fill("red"),
+                    attr::stroke("black"),
+                    attr::stroke_width("3"),
                 ),
-                svg::rect(
-                    (
-                        attr::x("100"),
-                        attr::y("10"),
-                        attr::width("40"),
-                        attr::height("40"),
-                        attr::fill("green"),
-                        attr::stroke("black"),
-                        attr::stroke_width("2"),
-                    ),
-                    (),
+                (),
+            ),
+            svg::rect(
+                (
+                    attr::x("100"),
+                    attr::y("10"),
+                    attr::width("40"),
+                    attr::height("40"),
+                    attr::fill("green"),
+                    attr::stroke("black"),
+                    attr::stroke_width("2"),
                 ),
-                svg::line(
-                    (
-                        attr::x1("20"),
-                        attr::y1("200"),
-                        attr::x2("200"),
-                        attr::y2("20"),
-                        attr::stroke("blue"),
-                        attr::stroke_width("10"),
-                        attr::stroke_linecap("round"),
-                    ),
-                    (),
+                (),
+            ),
+            svg::line(
+                (
+                    attr::x1("20"),
+                    attr::y1("200"),
+                    attr::x2("200"),
+                    attr::y2("20"),
+                    attr::stroke("blue"),
+                    attr::stroke_width("10"),
+                    attr::stroke_linecap("round"),
                 ),
-                svg::polyline(
-                    (
-                        attr::points(
-                            "200,40 \
+                (),
+            ),
+            svg::polyline(
+                (
+                    attr::points(
+                        "200,40 \
                              240,40 \
                              240,80 \
                              280,80 \
                              280,120 \
                              320,120 \
                              320,160",
-                        ),
-                        attr::fill("none"),
-                        attr::stroke("red"),
-                        attr::stroke_width("4"),
-                        attr::stroke_dasharray("20,2"),
                     ),
-                    (),
+                    attr::fill("none"),
+                    attr::stroke("red"),
+                    attr::stroke_width("4"),
+                    attr::stroke_dasharray("20,2"),
                 ),
-                svg::text(
-                    (
-                        attr::x("130"),
-                        attr::y("130"),
-                        attr::fill("black"),
-                        attr::text_anchor("middle"),
-                        attr::dominant_baseline("central"),
-                        attr::transform("rotate(-45 130,130)"),
-                    ),
-                    "Welcome to Shape Club",
+                (),
+            ),
+            svg::text(
+                (
+                    attr::x("130"),
+                    attr::y("130"),
+                    attr::fill("black"),
+                    attr::text_anchor("middle"),
+                    attr::dominant_baseline("central"),
+                    attr::transform("rotate(-45 130,130)"),
                 ),
+                "Welcome to Shape Club",
             ),
-        )
-    })?;
+        ),
+    )
+}
 
-    Ok(())
+/// The same shapes drawn on `<canvas>`.
+fn canvas_shapes() -> impl siro::vdom::Nodes<()> {
+    use siro::{
+        canvas::{drawing, Drawing},
+        html::canvas,
+        vdom::attribute,
+    };
+    use std::f64::consts::PI;
+
+    canvas(
+        (
+            attribute("width", "400"),
+            attribute("height", "400"),
+            drawing(
+                Drawing::new()
+                    // circle
+                    .begin_path()
+                    .arc(50.0, 50.0, 40.0, 0.0, 2.0 * PI)
+                    .fill_style("red")
+                    .fill()
+                    .stroke_style("black")
+                    .line_width(3.0)
+                    .stroke()
+                    // rect
+                    .fill_style("green")
+                    .fill_rect(100.0, 10.0, 40.0, 40.0)
+                    .line_width(2.0)
+                    .stroke_rect(100.0, 10.0, 40.0, 40.0)
+                    // line
+                    .begin_path()
+                    .move_to(20.0, 200.0)
+                    .line_to(200.0, 20.0)
+                    .stroke_style("blue")
+                    .line_width(10.0)
+                    .line_cap("round")
+                    .stroke()
+                    // polyline
+                    .begin_path()
+                    .move_to(200.0, 40.0)
+                    .line_to(240.0, 40.0)
+                    .line_to(240.0, 80.0)
+                    .line_to(280.0, 80.0)
+                    .line_to(280.0, 120.0)
+                    .line_to(320.0, 120.0)
+                    .line_to(320.0, 160.0)
+                    .stroke_style("red")
+                    .line_width(4.0)
+                    .line_cap("butt")
+                    .line_dash(vec![20.0, 2.0])
+                    .stroke()
+                    // text
+                    .save()
+                    .translate(130.0, 130.0)
+                    .rotate(-PI / 4.0)
+                    .fill_style("black")
+                    .text_align("center")
+                    .text_baseline("middle")
+                    .fill_text("Welcome to Shape Club", 0.0, 0.0)
+                    .restore(),
+            ),
+        ),
+        (),
+    )
 }
@@ -2,14 +2,14 @@ use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use siro::prelude::*;
 use siro::{
-    effects::{DomFocus, Effects},
+    effects::{DomFocus, Storage},
     html::{
         self, attr,
         event::{on_blur, on_click, on_double_click, on_enter, on_input},
     },
+    router::Router,
     vdom::class,
 };
-use std::str::FromStr;
 
 // ==== model ====
 
@@ -22,6 +22,8 @@ pub struct Model {
     pub visibility: Option<Visibility>,
 }
 
+pub const STORAGE_KEY: &str = "siro-todomvc-save";
+
 pub type TodoId = ulid::Ulid;
 
 fn todo_edit_input_id(id: TodoId) -> String {
@@ -37,26 +39,17 @@ pub struct TodoEntry {
     pub editing: bool,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Deserialize, Serialize)]
-pub enum Visibility {
-    All,
-    Active,
-    Completed,
-}
-
-impl FromStr for Visibility {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match &*s.trim().to_lowercase() {
-            "all" => Ok(Visibility::All),
-            "active" => Ok(Visibility::Active),
-            "completed" => Ok(Visibility::Completed),
-            _ => Err(()),
-        }
+siro::routes! {
+    #[derive(Copy, Clone, Debug, PartialEq, Deserialize, Serialize)]
+    pub enum Visibility {
+        All => [],
+        Active => ["active"],
+        Completed => ["completed"],
     }
 }
 
+pub const ROUTER: Router = Router::hash();
+
 // ==== update ====
 
 pub enum Msg {
@@ -71,13 +64,9 @@ pub enum Msg {
     EditingEntry(TodoId, bool),
 }
 
-pub trait SaveModel: Effects {
-    fn save_model(&mut self, model: &Model) -> Result<(), Self::Error>;
-}
-
 pub fn update<E>(model: &mut Model, msg: Msg, mut effects: E) -> Result<E::Ok, E::Error>
 where
-    E: DomFocus + SaveModel,
+    E: DomFocus + Storage,
 {
     match msg {
         Msg::UpdateField(input) => {
@@ -97,14 +86,14 @@ where
                         editing: false,
                     },
                 );
-                effects.save_model(model)?;
+                effects.set_item(STORAGE_KEY, model)?;
             }
         }
 
         Msg::Check(id, completed) => {
             if let Some(entry) = model.entries.get_mut(&id) {
                 entry.completed = completed;
-                effects.save_model(model)?;
+                effects.set_item(STORAGE_KEY, model)?;
             }
         }
 
@@ -112,18 +101,18 @@ where
             for entry in model.entries.values_mut() {
                 entry.completed = completed;
             }
-            effects.save_model(model)?;
+            effects.set_item(STORAGE_KEY, model)?;
         }
 
         Msg::Delete(id) => {
             if let Some(..) = model.entries.remove(&id) {
-                effects.save_model(model)?;
+                effects.set_item(STORAGE_KEY, model)?;
             }
         }
 
         Msg::DeleteCompleted => {
             model.entries.retain(|_, entry| !entry.completed);
-            effects.save_model(model)?;
+            effects.set_item(STORAGE_KEY, model)?;
         }
 
         Msg::ChangeVisibility(visibility) => {
@@ -133,7 +122,7 @@ where
         Msg::UpdateEntry(id, description) => {
             if let Some(entry) = model.entries.get_mut(&id) {
                 entry.description = description;
-                effects.save_model(model)?;
+                effects.set_item(STORAGE_KEY, model)?;
             }
         }
 
@@ -292,9 +281,9 @@ fn view_controls(model: &Model) -> impl Nodes<Msg> {
             html::ul(
                 class("filters"),
                 (
-                    view_visibility_swap(model, Visibility::All, "All", "#/"),
-                    view_visibility_swap(model, Visibility::Active, "Active", "#/active"),
-                    view_visibility_swap(model, Visibility::Completed, "Completed", "#/completed"),
+                    view_visibility_swap(model, Visibility::All, "All"),
+                    view_visibility_swap(model, Visibility::Active, "Active"),
+                    view_visibility_swap(model, Visibility::Completed, "Completed"),
                 ),
             ),
             if_then(has_completed, || {
@@ -307,17 +296,15 @@ fn view_controls(model: &Model) -> impl Nodes<Msg> {
     )
 }
 
-fn view_visibility_swap(
-    model: &Model,
-    v: Visibility,
-    text: &'static str,
-    url: &'static str,
-) -> impl Nodes<Msg> {
+fn view_visibility_swap(model: &Model, v: Visibility, text: &'static str) -> impl Nodes<Msg> {
     let selected = model.visibility.map_or(false, |vis| vis == v);
     html::li(
         html::event::on_click(move || Msg::ChangeVisibility(v)),
         html::a(
-            (attr::href(url), if_then(selected, || class("selected"))),
+            (
+                attr::href(ROUTER.url_for(&v)),
+                if_then(selected, || class("selected")),
+            ),
             text,
         ),
     )
@@ -0,0 +1,297 @@
+//! Declarative 2D drawing on `<canvas>` elements.
+//!
+//! A `Drawing` is a list of the commands of the canvas 2D context, which is
+//! attached to a `<canvas>` element by the `drawing` attribute.  The runtime
+//! replays the commands onto the element whenever the drawing is changed.
+//!
+//! ```
+//! use siro::{canvas::{drawing, Drawing}, html::canvas, vdom::{attribute, Nodes}};
+//!
+//! fn view<TMsg: 'static>(angle: f64) -> impl Nodes<TMsg> {
+//!     canvas(
+//!         (
+//!             attribute("width", "200"),
+//!             attribute("height", "200"),
+//!             drawing(
+//!                 Drawing::new()
+//!                     .translate(100.0, 100.0)
+//!                     .rotate(angle)
+//!                     .fill_style("tomato")
+//!                     .fill_rect(-50.0, -50.0, 100.0, 100.0),
+//!             ),
+//!         ),
+//!         (),
+//!     )
+//! }
+//! ```
+
+use crate::vdom::{Attributes, AttributesRenderer, CowStr};
+use std::iter::FromIterator;
+
+/// A drawing command of the canvas 2D context.
+///
+/// The commands correspond to the methods and properties of
+/// `CanvasRenderingContext2D` with the same names.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Command {
+    Save,
+    Restore,
+
+    // paths
+    BeginPath,
+    ClosePath,
+    MoveTo {
+        x: f64,
+        y: f64,
+    },
+    LineTo {
+        x: f64,
+        y: f64,
+    },
+    QuadraticCurveTo {
+        cpx: f64,
+        cpy: f64,
+        x: f64,
+        y: f64,
+    },
+    BezierCurveTo {
+        cp1x: f64,
+        cp1y: f64,
+        cp2x: f64,
+        cp2y: f64,
+        x: f64,
+        y: f64,
+    },
+    Arc {
+        x: f64,
+        y: f64,
+        radius: f64,
+        start_angle: f64,
+        end_angle: f64,
+        anticlockwise: bool,
+    },
+    Rect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    },
+    Fill,
+    Stroke,
+    Clip,
+
+    // rectangles
+    FillRect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    },
+    StrokeRect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    },
+    ClearRect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    },
+
+    // text
+    FillText {
+        text: CowStr,
+        x: f64,
+        y: f64,
+    },
+    StrokeText {
+        text: CowStr,
+        x: f64,
+        y: f64,
+    },
+
+    // images
+    DrawImage {
+        src: CowStr,
+        x: f64,
+        y: f64,
+        size: Option<(f64, f64)>,
+    },
+
+    // transforms
+    Translate {
+        x: f64,
+        y: f64,
+    },
+    Rotate {
+        angle: f64,
+    },
+    Scale {
+        x: f64,
+        y: f64,
+    },
+    Transform {
+        a: f64,
+        b: f64,
+        c: f64,
+        d: f64,
+        e: f64,
+        f: f64,
+    },
+    ResetTransform,
+
+    // styles
+    FillStyle(CowStr),
+    StrokeStyle(CowStr),
+    LineWidth(f64),
+    LineCap(CowStr),
+    LineJoin(CowStr),
+    LineDash(Vec<f64>),
+    Font(CowStr),
+    TextAlign(CowStr),
+    TextBaseline(CowStr),
+    GlobalAlpha(f64),
+}
+
+/// A list of drawing commands, built by chaining the methods.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Drawing {
+    commands: Vec<Command>,
+}
+
+macro_rules! commands {
+    ( $(
+        $(#[$m:meta])*
+        $name:ident ( $( $arg:ident : $ty:ty ),* ) => $command:expr;
+    )* ) => {$(
+        $(#[$m])*
+        #[inline]
+        pub fn $name(self, $( $arg : $ty ),*) -> Self {
+            self.command($command)
+        }
+    )*};
+}
+
+impl Drawing {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a command to this drawing.
+    #[inline]
+    pub fn command(mut self, command: Command) -> Self {
+        self.commands.push(command);
+        self
+    }
+
+    /// Append the commands in another drawing, e.g. a reusable part.
+    pub fn append(mut self, other: Drawing) -> Self {
+        self.commands.extend(other.commands);
+        self
+    }
+
+    /// Return the commands in this drawing.
+    pub fn commands(&self) -> &[Command] {
+        &self.commands[..]
+    }
+
+    commands! {
+        save() => Command::Save;
+        restore() => Command::Restore;
+
+        begin_path() => Command::BeginPath;
+        close_path() => Command::ClosePath;
+        move_to(x: f64, y: f64) => Command::MoveTo { x, y };
+        line_to(x: f64, y: f64) => Command::LineTo { x, y };
+        quadratic_curve_to(cpx: f64, cpy: f64, x: f64, y: f64) =>
+            Command::QuadraticCurveTo { cpx, cpy, x, y };
+        bezier_curve_to(cp1x: f64, cp1y: f64, cp2x: f64, cp2y: f64, x: f64, y: f64) =>
+            Command::BezierCurveTo { cp1x, cp1y, cp2x, cp2y, x, y };
+        /// Add a clockwise arc to the path.
+        arc(x: f64, y: f64, radius: f64, start_angle: f64, end_angle: f64) =>
+            Command::Arc { x, y, radius, start_angle, end_angle, anticlockwise: false };
+        /// Add an anticlockwise arc to the path.
+        arc_anticlockwise(x: f64, y: f64, radius: f64, start_angle: f64, end_angle: f64) =>
+            Command::Arc { x, y, radius, start_angle, end_angle, anticlockwise: true };
+        rect(x: f64, y: f64, width: f64, height: f64) => Command::Rect { x, y, width, height };
+        fill() => Command::Fill;
+        stroke() => Command::Stroke;
+        clip() => Command::Clip;
+
+        fill_rect(x: f64, y: f64, width: f64, height: f64) =>
+            Command::FillRect { x, y, width, height };
+        stroke_rect(x: f64, y: f64, width: f64, height: f64) =>
+            Command::StrokeRect { x, y, width, height };
+        clear_rect(x: f64, y: f64, width: f64, height: f64) =>
+            Command::ClearRect { x, y, width, height };
+
+        fill_text(text: impl Into<CowStr>, x: f64, y: f64) =>
+            Command::FillText { text: text.into(), x, y };
+        stroke_text(text: impl Into<CowStr>, x: f64, y: f64) =>
+            Command::StrokeText { text: text.into(), x, y };
+
+        /// Draw the image at the URL with its natural size.
+        ///
+        /// The image is drawn after it is loaded.
+        draw_image(src: impl Into<CowStr>, x: f64, y: f64) =>
+            Command::DrawImage { src: src.into(), x, y, size: None };
+        /// Draw the image at the URL scaled to the specified size.
+        draw_image_with_size(src: impl Into<CowStr>, x: f64, y: f64, width: f64, height: f64) =>
+            Command::DrawImage { src: src.into(), x, y, size: Some((width, height)) };
+
+        translate(x: f64, y: f64) => Command::Translate { x, y };
+        rotate(angle: f64) => Command::Rotate { angle };
+        scale(x: f64, y: f64) => Command::Scale { x, y };
+        transform(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) =>
+            Command::Transform { a, b, c, d, e, f };
+        reset_transform() => Command::ResetTransform;
+
+        fill_style(style: impl Into<CowStr>) => Command::FillStyle(style.into());
+        stroke_style(style: impl Into<CowStr>) => Command::StrokeStyle(style.into());
+        line_width(width: f64) => Command::LineWidth(width);
+        line_cap(cap: impl Into<CowStr>) => Command::LineCap(cap.into());
+        line_join(join: impl Into<CowStr>) => Command::LineJoin(join.into());
+        line_dash(segments: impl IntoIterator<Item = f64>) =>
+            Command::LineDash(segments.into_iter().collect());
+        font(font: impl Into<CowStr>) => Command::Font(font.into());
+        text_align(align: impl Into<CowStr>) => Command::TextAlign(align.into());
+        text_baseline(baseline: impl Into<CowStr>) => Command::TextBaseline(baseline.into());
+        global_alpha(alpha: f64) => Command::GlobalAlpha(alpha);
+    }
+}
+
+impl FromIterator<Command> for Drawing {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Command>,
+    {
+        Self {
+            commands: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Create an `Attr` that draws the drawing on the `<canvas>` element.
+///
+/// The canvas is cleared before the drawing is replayed, and the state of
+/// the context, such as the styles and the transform, does not carry over
+/// between the replays.
+#[inline]
+pub fn drawing<TMsg: 'static>(drawing: Drawing) -> impl Attributes<TMsg> {
+    SetDrawing(drawing)
+}
+
+struct SetDrawing(Drawing);
+
+impl<TMsg: 'static> Attributes<TMsg> for SetDrawing {
+    fn render_attributes<R>(self, mut renderer: R) -> Result<R::Ok, R::Error>
+    where
+        R: AttributesRenderer<Msg = TMsg>,
+    {
+        renderer.drawing(self.0)?;
+        renderer.end()
+    }
+}
@@ -0,0 +1,126 @@
+//! Commands for asynchronous effects.
+
+use std::{fmt, future::Future, pin::Pin};
+
+/// A boxed future that yields a message.
+pub type Task<TMsg> = Pin<Box<dyn Future<Output = TMsg> + 'static>>;
+
+/// A collection of asynchronous operations requested to the runtime.
+///
+/// Each operation is a future that yields a message.  The runtime executes
+/// them and delivers the resulting messages to the application.
+pub struct Cmd<TMsg> {
+    tasks: Vec<Task<TMsg>>,
+}
+
+impl<TMsg: 'static> Cmd<TMsg> {
+    /// Create a command that does nothing.
+    #[inline]
+    pub fn none() -> Self {
+        Self { tasks: vec![] }
+    }
+
+    /// Create a command that executes the given future and sends its output.
+    pub fn perform<F>(future: F) -> Self
+    where
+        F: Future<Output = TMsg> + 'static,
+    {
+        Self {
+            tasks: vec![Box::pin(future)],
+        }
+    }
+
+    /// Combine several commands into one.
+    pub fn batch<I>(cmds: I) -> Self
+    where
+        I: IntoIterator<Item = Cmd<TMsg>>,
+    {
+        Self {
+            tasks: cmds.into_iter().flat_map(|cmd| cmd.tasks).collect(),
+        }
+    }
+
+    /// Return whether this command contains no operations.
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Map the message type to another one.
+    pub fn map<F, UMsg>(self, f: F) -> Cmd<UMsg>
+    where
+        F: Fn(TMsg) -> UMsg + Clone + 'static,
+        UMsg: 'static,
+    {
+        Cmd {
+            tasks: self
+                .tasks
+                .into_iter()
+                .map(|task| {
+                    let f = f.clone();
+                    Box::pin(async move { f(task.await) }) as Task<UMsg>
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<TMsg: 'static> Default for Cmd<TMsg> {
+    #[inline]
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl<TMsg> fmt::Debug for Cmd<TMsg> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cmd")
+            .field("num_tasks", &self.tasks.len())
+            .finish()
+    }
+}
+
+impl<TMsg> IntoIterator for Cmd<TMsg> {
+    type Item = Task<TMsg>;
+    type IntoIter = std::vec::IntoIter<Task<TMsg>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.tasks.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+
+    fn run<TMsg>(cmd: Cmd<TMsg>) -> Vec<TMsg> {
+        cmd.into_iter().map(block_on).collect()
+    }
+
+    #[test]
+    fn none_is_empty() {
+        assert!(Cmd::<()>::none().is_none());
+        assert!(Cmd::<()>::default().is_none());
+        assert!(!Cmd::perform(async { 1 }).is_none());
+    }
+
+    #[test]
+    fn batch_keeps_order() {
+        let cmd = Cmd::batch(vec![
+            Cmd::perform(async { 1 }),
+            Cmd::none(),
+            Cmd::batch(vec![Cmd::perform(async { 2 }), Cmd::perform(async { 3 })]),
+        ]);
+        assert_eq!(run(cmd), [1, 2, 3]);
+    }
+
+    #[test]
+    fn map_applies_to_each_task() {
+        let cmd = Cmd::batch(vec![Cmd::perform(async { 1 }), Cmd::perform(async { 2 })])
+            .map(|n: i32| n.to_string());
+        assert_eq!(run(cmd), ["1", "2"]);
+        assert!(Cmd::<i32>::none().map(|n| n + 1).is_none());
+    }
+}
@@ -1,5 +1,10 @@
 //! Managed effects.
 
+pub mod testing;
+
+use serde::{de::DeserializeOwned, Serialize};
+use std::time::Duration;
+
 /// The base trait that represents managed effects.
 pub trait Effects {
     type Ok;
@@ -55,3 +60,113 @@ pub trait Navigation: Effects {
         Ok(())
     }
 }
+
+/// A mix-in that accesses the persistent key-value storage.
+///
+/// The stored values are serialized by `serde`.
+pub trait Storage: Effects {
+    /// Retrieve the value associated with the key.
+    #[allow(unused_variables)]
+    fn get_item<T>(&mut self, key: &str) -> Result<Option<T>, Self::Error>
+    where
+        T: DeserializeOwned,
+    {
+        Ok(None)
+    }
+
+    /// Store the value with the key, overwriting the previous one.
+    #[allow(unused_variables)]
+    fn set_item<T>(&mut self, key: &str, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        Ok(())
+    }
+
+    /// Remove the value associated with the key.
+    #[allow(unused_variables)]
+    fn remove_item(&mut self, key: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// A mix-in that sends messages after a delay.
+pub trait Timers<TMsg>: Effects {
+    /// Send a message after the specified duration has elapsed.
+    #[allow(unused_variables)]
+    fn after(&mut self, duration: Duration, msg: TMsg) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// A mix-in that controls scroll positions.
+pub trait Scroll: Effects {
+    /// Find DOM node by id string and scroll its ancestors so that it becomes visible.
+    #[allow(unused_variables)]
+    fn scroll_into_view(&mut self, target_id: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Scroll the document to the specified coordinates.
+    #[allow(unused_variables)]
+    fn scroll_to(&mut self, x: f64, y: f64) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// A mix-in that accesses the system clipboard.
+pub trait Clipboard: Effects {
+    /// Write a text to the clipboard.
+    #[allow(unused_variables)]
+    fn write_text(&mut self, text: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// A mix-in that outputs logs to the console.
+pub trait Console: Effects {
+    /// Output an informational message.
+    #[allow(unused_variables)]
+    fn log(&mut self, message: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Output a warning message.
+    #[allow(unused_variables)]
+    fn warn(&mut self, message: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Output an error message.
+    #[allow(unused_variables)]
+    fn error(&mut self, message: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// A mix-in that controls the title of document.
+pub trait DocumentTitle: Effects {
+    /// Change the title of document.
+    #[allow(unused_variables)]
+    fn set_title(&mut self, title: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// The set of all mix-ins, which the effects passed to `Program::update` implement.
+pub trait AllEffects<TMsg>:
+    DomFocus + Navigation + Storage + Timers<TMsg> + Scroll + Clipboard + Console + DocumentTitle
+{
+}
+
+impl<T, TMsg> AllEffects<TMsg> for T where
+    T: DomFocus
+        + Navigation
+        + Storage
+        + Timers<TMsg>
+        + Scroll
+        + Clipboard
+        + Console
+        + DocumentTitle
+{
+}
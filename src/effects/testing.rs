@@ -0,0 +1,332 @@
+//! Test doubles for managed effects.
+
+use super::{
+    Clipboard, Console, DocumentTitle, DomFocus, Effects, Navigation, Scroll, Storage, Timers,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{collections::HashMap, fmt, time::Duration};
+
+/// A call of the effect method recorded by `Recorder`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Call<TMsg> {
+    Focus(String),
+    Blur(String),
+    PushUrl(String),
+    ReplaceUrl(String),
+    Back(usize),
+    Forward(usize),
+    Load(String),
+    Reload(bool),
+    GetItem(String),
+    /// The value is recorded as a JSON string.
+    SetItem(String, String),
+    RemoveItem(String),
+    After(Duration, TMsg),
+    ScrollIntoView(String),
+    ScrollTo(f64, f64),
+    WriteText(String),
+    Log(String),
+    Warn(String),
+    Error(String),
+    SetTitle(String),
+}
+
+/// The error type returned from `Recorder`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    message: String,
+}
+
+impl Error {
+    /// Create an error with the given message.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// An implementation of `Effects` that records each call of the effect methods.
+///
+/// The recorder implements all of the built-in mix-ins and returns the
+/// recorded calls in order from `Effects::end`.  The storage is emulated
+/// in memory so that the values written by `set_item` can be read back.
+pub struct Recorder<TMsg> {
+    calls: Vec<Call<TMsg>>,
+    items: HashMap<String, String>,
+    failures: Vec<Failure<TMsg>>,
+}
+
+type Predicate<TMsg> = Box<dyn FnMut(&Call<TMsg>) -> bool>;
+
+struct Failure<TMsg> {
+    predicate: Predicate<TMsg>,
+    message: String,
+}
+
+impl<TMsg> Default for Recorder<TMsg> {
+    fn default() -> Self {
+        Self {
+            calls: vec![],
+            items: HashMap::new(),
+            failures: vec![],
+        }
+    }
+}
+
+impl<TMsg> fmt::Debug for Recorder<TMsg>
+where
+    TMsg: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recorder")
+            .field("calls", &self.calls)
+            .field("items", &self.items)
+            .finish()
+    }
+}
+
+impl<TMsg> Recorder<TMsg> {
+    /// Create a new `Recorder`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store a value in the emulated storage, which is returned from `get_item`.
+    ///
+    /// This method is not recorded as a call.
+    pub fn insert_item<T>(&mut self, key: impl Into<String>, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let encoded = serde_json::to_string(value).map_err(|err| Error::new(err.to_string()))?;
+        self.items.insert(key.into(), encoded);
+        Ok(())
+    }
+
+    /// Make the calls that match the predicate fail with the given message.
+    ///
+    /// The failed calls are still recorded.
+    pub fn fail_on<F>(&mut self, predicate: F, message: impl Into<String>)
+    where
+        F: FnMut(&Call<TMsg>) -> bool + 'static,
+    {
+        self.failures.push(Failure {
+            predicate: Box::new(predicate),
+            message: message.into(),
+        });
+    }
+
+    /// Return the calls recorded so far.
+    pub fn calls(&self) -> &[Call<TMsg>] {
+        &self.calls[..]
+    }
+
+    fn record(&mut self, call: Call<TMsg>) -> Result<(), Error> {
+        let mut result = Ok(());
+        for failure in &mut self.failures {
+            if (failure.predicate)(&call) {
+                result = Err(Error::new(failure.message.clone()));
+                break;
+            }
+        }
+        self.calls.push(call);
+        result
+    }
+}
+
+impl<TMsg> Effects for Recorder<TMsg> {
+    type Ok = Vec<Call<TMsg>>;
+    type Error = Error;
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.calls)
+    }
+}
+
+impl<TMsg> DomFocus for Recorder<TMsg> {
+    fn focus(&mut self, target_id: &str) -> Result<(), Self::Error> {
+        self.record(Call::Focus(target_id.to_owned()))
+    }
+
+    fn blur(&mut self, target_id: &str) -> Result<(), Self::Error> {
+        self.record(Call::Blur(target_id.to_owned()))
+    }
+}
+
+impl<TMsg> Navigation for Recorder<TMsg> {
+    fn push_url(&mut self, url: &str) -> Result<(), Self::Error> {
+        self.record(Call::PushUrl(url.to_owned()))
+    }
+
+    fn replace_url(&mut self, url: &str) -> Result<(), Self::Error> {
+        self.record(Call::ReplaceUrl(url.to_owned()))
+    }
+
+    fn back(&mut self, count: usize) -> Result<(), Self::Error> {
+        self.record(Call::Back(count))
+    }
+
+    fn forward(&mut self, count: usize) -> Result<(), Self::Error> {
+        self.record(Call::Forward(count))
+    }
+
+    fn load(&mut self, url: &str) -> Result<(), Self::Error> {
+        self.record(Call::Load(url.to_owned()))
+    }
+
+    fn reload(&mut self, skip_cache: bool) -> Result<(), Self::Error> {
+        self.record(Call::Reload(skip_cache))
+    }
+}
+
+impl<TMsg> Storage for Recorder<TMsg> {
+    fn get_item<T>(&mut self, key: &str) -> Result<Option<T>, Self::Error>
+    where
+        T: DeserializeOwned,
+    {
+        self.record(Call::GetItem(key.to_owned()))?;
+        match self.items.get(key) {
+            Some(encoded) => serde_json::from_str(encoded)
+                .map(Some)
+                .map_err(|err| Error::new(err.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    fn set_item<T>(&mut self, key: &str, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let encoded = serde_json::to_string(value).map_err(|err| Error::new(err.to_string()))?;
+        self.record(Call::SetItem(key.to_owned(), encoded.clone()))?;
+        self.items.insert(key.to_owned(), encoded);
+        Ok(())
+    }
+
+    fn remove_item(&mut self, key: &str) -> Result<(), Self::Error> {
+        self.record(Call::RemoveItem(key.to_owned()))?;
+        self.items.remove(key);
+        Ok(())
+    }
+}
+
+impl<TMsg> Timers<TMsg> for Recorder<TMsg> {
+    fn after(&mut self, duration: Duration, msg: TMsg) -> Result<(), Self::Error> {
+        self.record(Call::After(duration, msg))
+    }
+}
+
+impl<TMsg> Scroll for Recorder<TMsg> {
+    fn scroll_into_view(&mut self, target_id: &str) -> Result<(), Self::Error> {
+        self.record(Call::ScrollIntoView(target_id.to_owned()))
+    }
+
+    fn scroll_to(&mut self, x: f64, y: f64) -> Result<(), Self::Error> {
+        self.record(Call::ScrollTo(x, y))
+    }
+}
+
+impl<TMsg> Clipboard for Recorder<TMsg> {
+    fn write_text(&mut self, text: &str) -> Result<(), Self::Error> {
+        self.record(Call::WriteText(text.to_owned()))
+    }
+}
+
+impl<TMsg> Console for Recorder<TMsg> {
+    fn log(&mut self, message: &str) -> Result<(), Self::Error> {
+        self.record(Call::Log(message.to_owned()))
+    }
+
+    fn warn(&mut self, message: &str) -> Result<(), Self::Error> {
+        self.record(Call::Warn(message.to_owned()))
+    }
+
+    fn error(&mut self, message: &str) -> Result<(), Self::Error> {
+        self.record(Call::Error(message.to_owned()))
+    }
+}
+
+impl<TMsg> DocumentTitle for Recorder<TMsg> {
+    fn set_title(&mut self, title: &str) -> Result<(), Self::Error> {
+        self.record(Call::SetTitle(title.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Msg {
+        Saved,
+    }
+
+    /// Restore the counter from the storage and save the incremented one,
+    /// as an `update` function would do.
+    fn increment<E>(effects: &mut E) -> Result<(), E::Error>
+    where
+        E: Storage + Timers<Msg>,
+    {
+        let count: i32 = effects.get_item("count")?.unwrap_or(0);
+        effects.set_item("count", &(count + 1))?;
+        effects.after(Duration::from_secs(1), Msg::Saved)
+    }
+
+    #[test]
+    fn records_calls_through_mixins() {
+        let mut recorder = Recorder::new();
+        recorder.insert_item("count", &41).unwrap();
+
+        increment(&mut recorder).unwrap();
+        assert_eq!(recorder.get_item::<i32>("count"), Ok(Some(42)));
+        assert_eq!(
+            recorder.calls(),
+            [
+                Call::GetItem("count".into()),
+                Call::SetItem("count".into(), "42".into()),
+                Call::After(Duration::from_secs(1), Msg::Saved),
+                Call::GetItem("count".into()),
+            ]
+        );
+
+        recorder.remove_item("count").unwrap();
+        assert_eq!(recorder.get_item::<i32>("count"), Ok(None));
+        assert_eq!(recorder.end().unwrap().len(), 6);
+    }
+
+    #[test]
+    fn fail_on_matching_calls() {
+        let mut recorder = Recorder::new();
+        recorder.fail_on(
+            |call| matches!(call, Call::SetItem(key, _) if key == "count"),
+            "quota exceeded",
+        );
+
+        assert_eq!(increment(&mut recorder), Err(Error::new("quota exceeded")));
+        // The failed call is recorded, but the value is not stored.
+        assert_eq!(
+            recorder.calls(),
+            [
+                Call::GetItem("count".into()),
+                Call::SetItem("count".into(), "1".into()),
+            ]
+        );
+        assert_eq!(recorder.get_item::<i32>("count"), Ok(None));
+
+        // The calls that do not match the predicate succeed.
+        recorder.set_item("other", "value").unwrap();
+        assert_eq!(
+            recorder.get_item::<String>("other"),
+            Ok(Some("value".into()))
+        );
+    }
+}
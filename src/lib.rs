@@ -10,11 +10,18 @@ detail of runtime implementation.
 #![doc(html_root_url = "https://docs.rs/siro/0.1.0")]
 #![forbid(unsafe_code, clippy::todo, clippy::unimplemented)]
 
+pub mod canvas;
+pub mod cmd;
 pub mod effects;
 pub mod html;
+pub mod router;
 pub mod svg;
 pub mod vdom;
 
+mod program;
+
+pub use crate::{cmd::Cmd, program::Program};
+
 /// A *prelude* for end users.
 pub mod prelude {
     #[doc(no_inline)]
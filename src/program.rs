@@ -0,0 +1,45 @@
+//! Elm-style application programs.
+
+use crate::{cmd::Cmd, effects::AllEffects, vdom::NodesRenderer};
+
+/// An application built on the Elm architecture.
+///
+/// A `Program` describes how its model is initialized, how the messages
+/// update the model, and how the model is converted to virtual DOM nodes
+/// and subscriptions.  The runtime drives these functions and is
+/// responsible for the rendering and the delivery of messages.
+pub trait Program {
+    /// The type of model that holds the application state.
+    type Model;
+
+    /// The message type that updates the model.
+    type Msg: 'static;
+
+    /// The type of subscriptions that the program listens to.
+    ///
+    /// The concrete requirements of this type are determined by the runtime.
+    type Subscriptions;
+
+    /// Create the initial model.
+    fn init(&self) -> Self::Model;
+
+    /// Apply a message to the model.
+    ///
+    /// The managed effects, such as focusing or storing values, are requested
+    /// through `effects`.  The returned command is executed by the runtime,
+    /// and the messages yielded from it are passed to this method again.
+    fn update<E>(&self, model: &mut Self::Model, msg: Self::Msg, effects: &mut E) -> Cmd<Self::Msg>
+    where
+        E: AllEffects<Self::Msg>;
+
+    /// Render the model using the given renderer.
+    ///
+    /// The implementation typically builds a value of `Nodes` from the
+    /// model and calls `render_nodes` with it.
+    fn view<R>(&self, model: &Self::Model, renderer: R) -> Result<R::Ok, R::Error>
+    where
+        R: NodesRenderer<Msg = Self::Msg>;
+
+    /// Return the subscriptions that the program listens to in the current state.
+    fn subscriptions(&self, model: &Self::Model) -> Self::Subscriptions;
+}
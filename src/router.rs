@@ -0,0 +1,549 @@
+//! Type-safe URL routing.
+//!
+//! The routes of application are declared as an enum by using the [`routes!`]
+//! macro, and `Router` converts between URLs and the values of the enum.
+//!
+//! [`routes!`]: ../macro.routes.html
+
+use std::{borrow::Cow, fmt, str::FromStr};
+
+/// A type that represents the routes of application.
+pub trait Route: Sized {
+    /// Convert a parsed path into the route.
+    ///
+    /// This method returns `None` if the path does not match any routes.
+    fn from_path(path: &Path) -> Option<Self>;
+
+    /// Render the route into the path string, including the query string.
+    fn to_path(&self) -> String;
+}
+
+/// The part of the URL that routing is based on.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Mode {
+    /// Routes are determined by the path and the query string of URL.
+    Path,
+    /// Routes are determined by the fragment of URL, such as `#/users/42`.
+    Hash,
+}
+
+/// A converter between URLs and routes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Router {
+    mode: Mode,
+}
+
+impl Router {
+    /// Create a `Router` with the specified mode.
+    #[inline]
+    pub const fn new(mode: Mode) -> Self {
+        Self { mode }
+    }
+
+    /// Create a `Router` that uses the path of URL.
+    #[inline]
+    pub const fn path() -> Self {
+        Self::new(Mode::Path)
+    }
+
+    /// Create a `Router` that uses the fragment of URL.
+    #[inline]
+    pub const fn hash() -> Self {
+        Self::new(Mode::Hash)
+    }
+
+    /// Return the mode of this router.
+    #[inline]
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Parse a URL into the route.
+    ///
+    /// The URL may be either absolute or relative to the origin.
+    pub fn parse<R>(&self, url: &str) -> Option<R>
+    where
+        R: Route,
+    {
+        R::from_path(&Path::parse(self.extract(url)))
+    }
+
+    /// Render the route into a URL relative to the origin.
+    pub fn url_for<R>(&self, route: &R) -> String
+    where
+        R: Route,
+    {
+        match self.mode {
+            Mode::Path => route.to_path(),
+            Mode::Hash => format!("#{}", route.to_path()),
+        }
+    }
+
+    fn extract<'a>(&self, url: &'a str) -> &'a str {
+        match self.mode {
+            Mode::Path => {
+                let url = match url.find("://") {
+                    Some(pos) => {
+                        let rest = &url[pos + 3..];
+                        rest.find('/').map_or("", |pos| &rest[pos..])
+                    }
+                    None => url,
+                };
+                url.split('#').next().unwrap_or("")
+            }
+            Mode::Hash => url.split_once('#').map_or("", |(_, fragment)| fragment),
+        }
+    }
+}
+
+// ==== Path ====
+
+/// A parsed path with the query parameters.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Path {
+    segments: Vec<String>,
+    query: Vec<(String, String)>,
+}
+
+impl Path {
+    /// Parse a string like `/users/42?tab=posts` into a `Path`.
+    ///
+    /// The path segments and query parameters are percent-decoded.
+    pub fn parse(s: &str) -> Self {
+        let s = s.split('#').next().unwrap_or("");
+        let (path, query) = s.split_once('?').unwrap_or((s, ""));
+
+        let segments = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| decode(segment, false).into_owned())
+            .collect();
+
+        let query = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (
+                    decode(name, true).into_owned(),
+                    decode(value, true).into_owned(),
+                )
+            })
+            .collect();
+
+        Self { segments, query }
+    }
+
+    /// Return the path segments.
+    pub fn segments(&self) -> impl Iterator<Item = &str> + '_ {
+        self.segments.iter().map(|segment| &**segment)
+    }
+
+    /// Return the value of the first query parameter with the given name.
+    pub fn query(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| &**value)
+    }
+}
+
+/// A builder of the path string.
+#[derive(Debug, Default)]
+pub struct PathBuilder {
+    path: String,
+    query: String,
+}
+
+impl PathBuilder {
+    /// Create an empty `PathBuilder`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a path segment, which is percent-encoded.
+    pub fn segment(&mut self, segment: impl fmt::Display) -> &mut Self {
+        self.path.push('/');
+        encode(&segment.to_string(), &mut self.path);
+        self
+    }
+
+    /// Append a query parameter, which is percent-encoded.
+    pub fn query(&mut self, name: &str, value: impl fmt::Display) -> &mut Self {
+        if !self.query.is_empty() {
+            self.query.push('&');
+        }
+        encode(name, &mut self.query);
+        self.query.push('=');
+        encode(&value.to_string(), &mut self.query);
+        self
+    }
+
+    /// Build the path string.
+    pub fn finish(&mut self) -> String {
+        let mut path = std::mem::take(&mut self.path);
+        if path.is_empty() {
+            path.push('/');
+        }
+        if !self.query.is_empty() {
+            path.push('?');
+            path += &std::mem::take(&mut self.query);
+        }
+        path
+    }
+}
+
+// ==== parameters ====
+
+/// A type that can be used as a query parameter in [`routes!`].
+///
+/// [`routes!`]: ../macro.routes.html
+pub trait QueryParam: Sized {
+    /// Convert the query value into this type.
+    ///
+    /// The returned value `None` means that the route does not match.
+    fn from_query(value: Option<&str>) -> Option<Self>;
+
+    /// Convert this value into the query value, or `None` to omit the parameter.
+    fn to_query(&self) -> Option<String>;
+}
+
+/// A missing parameter is converted into `None`, while a present but
+/// unparsable one makes the route not match.
+impl<T> QueryParam for Option<T>
+where
+    T: FromStr + fmt::Display,
+{
+    fn from_query(value: Option<&str>) -> Option<Self> {
+        match value {
+            Some(value) => value.parse().ok().map(Some),
+            None => Some(None),
+        }
+    }
+
+    fn to_query(&self) -> Option<String> {
+        self.as_ref().map(|value| value.to_string())
+    }
+}
+
+impl QueryParam for bool {
+    fn from_query(value: Option<&str>) -> Option<Self> {
+        Some(match value {
+            Some("false") | Some("0") => false,
+            Some(..) => true,
+            None => false,
+        })
+    }
+
+    fn to_query(&self) -> Option<String> {
+        if *self {
+            Some("true".into())
+        } else {
+            None
+        }
+    }
+}
+
+/// Declare an enum that implements [`Route`].
+///
+/// Each variant is followed by the list of path segments, where a string
+/// literal matches the segment exactly and an identifier captures the
+/// segment into the field of the same name using `FromStr` and `Display`.
+/// The query parameters are specified after `?`, and their fields must
+/// implement [`QueryParam`].
+///
+/// ```ignore
+/// siro::routes! {
+///     pub enum Route {
+///         Home => [],
+///         Post { id: u64 } => ["posts", id],
+///         Search { q: Option<String>, page: Option<u32> } => ["search"] ? [q, page],
+///     }
+/// }
+/// ```
+///
+/// [`Route`]: ./router/trait.Route.html
+/// [`QueryParam`]: ./router/trait.QueryParam.html
+#[macro_export]
+macro_rules! routes {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $Name:ident {
+            $(
+                $(#[$vmeta:meta])*
+                $Variant:ident $( { $( $field:ident : $ty:ty ),* $(,)? } )?
+                    => [ $( $seg:tt ),* $(,)? ]
+                    $( ? [ $( $query:ident ),* $(,)? ] )?
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $Name {
+            $(
+                $(#[$vmeta])*
+                $Variant $( { $( $field : $ty ),* } )?,
+            )*
+        }
+
+        impl $crate::router::Route for $Name {
+            fn from_path(path: &$crate::router::Path) -> Option<Self> {
+                $({
+                    #[allow(unused_mut, unused_variables)]
+                    fn parse(path: &$crate::router::Path) -> Option<$Name> {
+                        let mut segments = path.segments();
+                        $( $crate::routes!(@parse segments, $seg); )*
+                        if segments.next().is_some() {
+                            return None;
+                        }
+                        $($(
+                            let $query = $crate::router::QueryParam::from_query(
+                                path.query(stringify!($query)),
+                            )?;
+                        )*)?
+                        Some($Name::$Variant { $( $( $field ),* )? })
+                    }
+                    if let Some(route) = parse(path) {
+                        return Some(route);
+                    }
+                })*
+                None
+            }
+
+            #[allow(unused_mut, unused_variables)]
+            fn to_path(&self) -> String {
+                let mut builder = $crate::router::PathBuilder::new();
+                match self {
+                    $(
+                        $Name::$Variant { $( $( $field ),* )? } => {
+                            $( $crate::routes!(@render builder, $seg); )*
+                            $($(
+                                if let Some(value) = $crate::router::QueryParam::to_query($query) {
+                                    builder.query(stringify!($query), value);
+                                }
+                            )*)?
+                        }
+                    )*
+                }
+                builder.finish()
+            }
+        }
+    };
+
+    (@parse $segments:ident, $lit:literal) => {
+        if $segments.next()? != $lit {
+            return None;
+        }
+    };
+    (@parse $segments:ident, $param:ident) => {
+        let $param = $segments.next()?.parse().ok()?;
+    };
+
+    (@render $builder:ident, $lit:literal) => {
+        $builder.segment($lit);
+    };
+    (@render $builder:ident, $param:ident) => {
+        $builder.segment($param);
+    };
+}
+
+// ==== percent encoding ====
+
+fn encode(s: &str, buf: &mut String) {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+    for &b in s.as_bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                buf.push(b as char)
+            }
+            _ => {
+                buf.push('%');
+                buf.push(HEX[(b >> 4) as usize] as char);
+                buf.push(HEX[(b & 0x0F) as usize] as char);
+            }
+        }
+    }
+}
+
+fn decode(s: &str, plus_as_space: bool) -> Cow<'_, str> {
+    if !(s.contains('%') || plus_as_space && s.contains('+')) {
+        return Cow::Borrowed(s);
+    }
+
+    fn hex(b: u8) -> Option<u8> {
+        match b {
+            b'0'..=b'9' => Some(b - b'0'),
+            b'A'..=b'F' => Some(b - b'A' + 10),
+            b'a'..=b'f' => Some(b - b'a' + 10),
+            _ => None,
+        }
+    }
+
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => match (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                (Some(h), Some(l)) => {
+                    decoded.push(h << 4 | l);
+                    i += 3;
+                    continue;
+                }
+                _ => decoded.push(b'%'),
+            },
+            b'+' if plus_as_space => decoded.push(b' '),
+            b => decoded.push(b),
+        }
+        i += 1;
+    }
+
+    Cow::Owned(String::from_utf8_lossy(&decoded).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::routes! {
+        #[derive(Debug, PartialEq)]
+        enum TestRoute {
+            Home => [],
+            Post { id: String } => ["posts", id],
+            Comment { id: u64, index: u32 } => ["posts", id, "comments", index],
+            Search { q: Option<String>, page: Option<u32>, all: bool } => ["search"] ? [q, page, all],
+        }
+    }
+
+    fn round_trip(router: Router, route: TestRoute) {
+        let url = router.url_for(&route);
+        assert_eq!(router.parse::<TestRoute>(&url), Some(route), "{}", url);
+    }
+
+    #[test]
+    fn url_for_segments() {
+        let router = Router::path();
+        assert_eq!(router.url_for(&TestRoute::Home), "/");
+        assert_eq!(
+            router.url_for(&TestRoute::Comment { id: 42, index: 3 }),
+            "/posts/42/comments/3"
+        );
+        assert_eq!(
+            router.url_for(&TestRoute::Post {
+                id: "a b/c?d".into()
+            }),
+            "/posts/a%20b%2Fc%3Fd"
+        );
+    }
+
+    #[test]
+    fn parse_segments() {
+        let router = Router::path();
+        assert_eq!(router.parse("/"), Some(TestRoute::Home));
+        assert_eq!(router.parse(""), Some(TestRoute::Home));
+        assert_eq!(
+            router.parse("/posts/%E3%81%82"),
+            Some(TestRoute::Post { id: "あ".into() })
+        );
+        assert_eq!(
+            router.parse("/posts/42/comments/3"),
+            Some(TestRoute::Comment { id: 42, index: 3 })
+        );
+        assert_eq!(router.parse::<TestRoute>("/posts"), None);
+        assert_eq!(router.parse::<TestRoute>("/posts/1/comments/x"), None);
+        assert_eq!(router.parse::<TestRoute>("/posts/1/extra"), None);
+    }
+
+    #[test]
+    fn parse_trailing_slash() {
+        let router = Router::path();
+        assert_eq!(
+            router.parse("/posts/42/"),
+            Some(TestRoute::Post { id: "42".into() })
+        );
+        assert_eq!(
+            router.parse("/search/"),
+            Some(TestRoute::Search {
+                q: None,
+                page: None,
+                all: false,
+            })
+        );
+    }
+
+    #[test]
+    fn query_params() {
+        let router = Router::path();
+        assert_eq!(
+            router.url_for(&TestRoute::Search {
+                q: Some("rust & wasm".into()),
+                page: None,
+                all: true,
+            }),
+            "/search?q=rust%20%26%20wasm&all=true"
+        );
+        assert_eq!(
+            router.parse("/search?q=a+b&page=2&all=0"),
+            Some(TestRoute::Search {
+                q: Some("a b".into()),
+                page: Some(2),
+                all: false,
+            })
+        );
+        assert_eq!(
+            router.parse("/search?all"),
+            Some(TestRoute::Search {
+                q: None,
+                page: None,
+                all: true,
+            })
+        );
+        // An unparsable value does not silently become `None`.
+        assert_eq!(router.parse::<TestRoute>("/search?page=abc"), None);
+    }
+
+    #[test]
+    fn round_trips() {
+        for &router in &[Router::path(), Router::hash()] {
+            round_trip(router, TestRoute::Home);
+            round_trip(
+                router,
+                TestRoute::Post {
+                    id: "100% /#?&+=".into(),
+                },
+            );
+            round_trip(router, TestRoute::Comment { id: 1, index: 0 });
+            round_trip(
+                router,
+                TestRoute::Search {
+                    q: Some("a+b c".into()),
+                    page: Some(10),
+                    all: true,
+                },
+            );
+            round_trip(
+                router,
+                TestRoute::Search {
+                    q: None,
+                    page: None,
+                    all: false,
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn parse_absolute_urls() {
+        assert_eq!(
+            Router::path().parse("https://example.com/posts/42?x=1#top"),
+            Some(TestRoute::Post { id: "42".into() })
+        );
+        assert_eq!(
+            Router::hash().parse("https://example.com/app/#/posts/42"),
+            Some(TestRoute::Post { id: "42".into() })
+        );
+        assert_eq!(
+            Router::hash().parse("https://example.com/posts/42"),
+            Some(TestRoute::Home)
+        );
+    }
+}
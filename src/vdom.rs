@@ -1,9 +1,12 @@
 //! Representation of DOM nodes.
 
+mod lifecycle;
 mod map;
 
+pub use lifecycle::{node_ref, on_mount, on_unmount, on_update, Hook, HookKind, NodeRef};
 pub use map::Map;
 
+use crate::canvas::Drawing;
 use either::Either;
 use serde::{
     de::{self, Deserialize, Deserializer},
@@ -59,6 +62,19 @@ pub trait NodesRenderer {
     /// [`Text`]: https://developer.mozilla.org/en-US/docs/Web/API/Text
     fn text_node(&mut self, data: CowStr) -> Result<(), Self::Error>;
 
+    /// Render a virtual portal, whose children are placed into the container
+    /// specified by the selector instead of the current parent.
+    ///
+    /// The default implementation renders the children in place, for the
+    /// renderers that have no notion of separate containers.
+    fn portal<C>(&mut self, target: CowStr, children: C) -> Result<(), Self::Error>
+    where
+        C: Nodes<Self::Msg>,
+    {
+        let _ = target;
+        Nodes::render_nodes(children, self)
+    }
+
     /// Finalize the rendering process.
     fn end(self) -> Result<Self::Ok, Self::Error>;
 }
@@ -91,6 +107,14 @@ where
         (*self).text_node(data)
     }
 
+    #[inline]
+    fn portal<C>(&mut self, target: CowStr, children: C) -> Result<(), Self::Error>
+    where
+        C: Nodes<Self::Msg>,
+    {
+        (*self).portal(target, children)
+    }
+
     #[inline]
     fn end(self) -> Result<Self::Ok, Self::Error> {
         Ok(())
@@ -201,6 +225,39 @@ impl_nodes_for_tuples!(
     C11, C12, C13, C14, C15, C16, C17, C18, C19, C20
 );
 
+/// Create a `Nodes` that renders the children into the container specified
+/// by the selector, e.g. `"body"`, rather than the current parent.
+///
+/// The children remain a part of the tree where the portal is placed, so
+/// they are diffed, receive the events and are removed along with it.
+/// This is useful for modals and tooltips that must escape the parents
+/// with `overflow: hidden`.
+pub fn portal<TMsg: 'static>(
+    target: impl Into<CowStr>,
+    children: impl Nodes<TMsg>,
+) -> impl Nodes<TMsg> {
+    Portal { target, children }
+}
+
+struct Portal<T, C> {
+    target: T,
+    children: C,
+}
+
+impl<T, C, TMsg: 'static> Nodes<TMsg> for Portal<T, C>
+where
+    T: Into<CowStr>,
+    C: Nodes<TMsg>,
+{
+    fn render_nodes<R>(self, mut renderer: R) -> Result<R::Ok, R::Error>
+    where
+        R: NodesRenderer<Msg = TMsg>,
+    {
+        renderer.portal(self.target.into(), self.children)?;
+        renderer.end()
+    }
+}
+
 /// Create a `Nodes` from an iterator.
 pub fn iter<I, TMsg>(iter: I) -> impl Nodes<TMsg>
 where
@@ -280,6 +337,30 @@ pub trait AttributesRenderer {
     /// should be ignored.
     fn inner_html(&mut self, inner_html: CowStr) -> Result<(), Self::Error>;
 
+    /// Register a lifecycle hook to this element.
+    ///
+    /// The default implementation ignores the hook.
+    fn hook(&mut self, hook: Hook) -> Result<(), Self::Error> {
+        let _ = hook;
+        Ok(())
+    }
+
+    /// Attach a 2D drawing to this element, which is expected to be a `<canvas>`.
+    ///
+    /// The default implementation ignores the drawing.
+    fn drawing(&mut self, drawing: Drawing) -> Result<(), Self::Error> {
+        let _ = drawing;
+        Ok(())
+    }
+
+    /// Register a `NodeRef` that refers the node of this element.
+    ///
+    /// The default implementation ignores the reference.
+    fn node_ref(&mut self, node_ref: NodeRef) -> Result<(), Self::Error> {
+        let _ = node_ref;
+        Ok(())
+    }
+
     /// Complete the rendering of this element.
     fn end(self) -> Result<Self::Ok, Self::Error>;
 }
@@ -328,6 +409,21 @@ where
         (*self).inner_html(inner_html)
     }
 
+    #[inline]
+    fn hook(&mut self, hook: Hook) -> Result<(), Self::Error> {
+        (*self).hook(hook)
+    }
+
+    #[inline]
+    fn drawing(&mut self, drawing: Drawing) -> Result<(), Self::Error> {
+        (*self).drawing(drawing)
+    }
+
+    #[inline]
+    fn node_ref(&mut self, node_ref: NodeRef) -> Result<(), Self::Error> {
+        (*self).node_ref(node_ref)
+    }
+
     #[inline]
     fn end(self) -> Result<Self::Ok, Self::Error> {
         Ok(())
@@ -0,0 +1,193 @@
+use super::{Attributes, AttributesRenderer};
+use std::{any::Any, cell::RefCell, fmt, rc::Rc};
+
+/// The timing when a `Hook` is called.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HookKind {
+    /// After the element is created and inserted into the document.
+    Mount,
+    /// After the element is updated by re-rendering.
+    Update,
+    /// Before the element is removed from the document.
+    Unmount,
+}
+
+type HookFn = Rc<dyn Fn(&dyn Any)>;
+
+/// A lifecycle hook of an element.
+///
+/// The hook receives the node created by the runtime, whose concrete type
+/// depends on the runtime, e.g. `web_sys::Element` in `siro-web`.
+#[derive(Clone)]
+pub struct Hook {
+    kind: HookKind,
+    f: HookFn,
+}
+
+impl fmt::Debug for Hook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hook").field("kind", &self.kind).finish()
+    }
+}
+
+impl Hook {
+    /// Create a hook that is called with the node of type `T`.
+    ///
+    /// The function is not called if the node is not of type `T`.
+    pub fn new<T, F>(kind: HookKind, f: F) -> Self
+    where
+        T: Any,
+        F: Fn(&T) + 'static,
+    {
+        Self {
+            kind,
+            f: Rc::new(move |node: &dyn Any| {
+                if let Some(node) = node.downcast_ref::<T>() {
+                    f(node);
+                }
+            }),
+        }
+    }
+
+    pub fn kind(&self) -> HookKind {
+        self.kind
+    }
+
+    /// Call the hook with the node.
+    pub fn call(&self, node: &dyn Any) {
+        (self.f)(node)
+    }
+}
+
+/// A handle to refer the node created by the runtime.
+///
+/// The handle is filled when the element with `node_ref` attribute is
+/// created, and cleared when it is removed.  The clones of a handle share
+/// the same node.
+#[derive(Clone, Default)]
+pub struct NodeRef {
+    node: Rc<RefCell<Option<Box<dyn Any>>>>,
+}
+
+impl fmt::Debug for NodeRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodeRef")
+            .field("is_set", &self.node.borrow().is_some())
+            .finish()
+    }
+}
+
+impl PartialEq for NodeRef {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.node, &other.node)
+    }
+}
+
+impl NodeRef {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the referred node if it is set and of type `T`.
+    pub fn get<T>(&self) -> Option<T>
+    where
+        T: Any + Clone,
+    {
+        self.node.borrow().as_ref()?.downcast_ref::<T>().cloned()
+    }
+
+    /// Set the referred node.  This method is intended to be called by the runtime.
+    pub fn set<T: Any>(&self, node: T) {
+        self.node.replace(Some(Box::new(node)));
+    }
+
+    /// Clear the referred node if it is still `node`.  This method is intended
+    /// to be called by the runtime.
+    ///
+    /// The handle may have been moved to the node created in place of the
+    /// removed one, which must be kept regardless of the order of removal.
+    pub fn clear<T>(&self, node: &T)
+    where
+        T: Any + PartialEq,
+    {
+        let mut current = self.node.borrow_mut();
+        if current.as_ref().and_then(|n| n.downcast_ref::<T>()) == Some(node) {
+            current.take();
+        }
+    }
+}
+
+/// Create an `Attr` that registers the function called after the element is inserted.
+#[inline]
+pub fn on_mount<T, TMsg: 'static>(f: impl Fn(&T) + 'static) -> impl Attributes<TMsg>
+where
+    T: Any,
+{
+    AddHook(Hook::new(HookKind::Mount, f))
+}
+
+/// Create an `Attr` that registers the function called after the element is re-rendered.
+#[inline]
+pub fn on_update<T, TMsg: 'static>(f: impl Fn(&T) + 'static) -> impl Attributes<TMsg>
+where
+    T: Any,
+{
+    AddHook(Hook::new(HookKind::Update, f))
+}
+
+/// Create an `Attr` that registers the function called before the element is removed.
+#[inline]
+pub fn on_unmount<T, TMsg: 'static>(f: impl Fn(&T) + 'static) -> impl Attributes<TMsg>
+where
+    T: Any,
+{
+    AddHook(Hook::new(HookKind::Unmount, f))
+}
+
+struct AddHook(Hook);
+
+impl<TMsg: 'static> Attributes<TMsg> for AddHook {
+    fn render_attributes<R>(self, mut renderer: R) -> Result<R::Ok, R::Error>
+    where
+        R: AttributesRenderer<Msg = TMsg>,
+    {
+        renderer.hook(self.0)?;
+        renderer.end()
+    }
+}
+
+/// Create an `Attr` that fills the `NodeRef` with the node of this element.
+#[inline]
+pub fn node_ref<TMsg: 'static>(node_ref: &NodeRef) -> impl Attributes<TMsg> {
+    SetNodeRef(node_ref.clone())
+}
+
+struct SetNodeRef(NodeRef);
+
+impl<TMsg: 'static> Attributes<TMsg> for SetNodeRef {
+    fn render_attributes<R>(self, mut renderer: R) -> Result<R::Ok, R::Error>
+    where
+        R: AttributesRenderer<Msg = TMsg>,
+    {
+        renderer.node_ref(self.0)?;
+        renderer.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clear_only_the_released_node() {
+        let node_ref = NodeRef::new();
+        node_ref.set("old");
+        node_ref.set("new");
+
+        node_ref.clear(&"old");
+        assert_eq!(node_ref.get::<&str>(), Some("new"));
+
+        node_ref.clear(&"new");
+        assert_eq!(node_ref.get::<&str>(), None);
+    }
+}
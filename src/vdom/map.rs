@@ -5,9 +5,12 @@ use super::{
     CowStr,
     Event,
     EventDecoder,
+    Hook,
+    NodeRef,
     Nodes,
     NodesRenderer,
 };
+use crate::canvas::Drawing;
 use serde::Serialize;
 use std::marker::PhantomData;
 
@@ -103,6 +106,21 @@ where
         self.renderer.text_node(data)
     }
 
+    #[inline]
+    fn portal<C>(&mut self, target: CowStr, children: C) -> Result<(), Self::Error>
+    where
+        C: Nodes<Self::Msg>,
+    {
+        self.renderer.portal(
+            target,
+            MapChildren {
+                children,
+                f: self.f,
+                _marker: PhantomData,
+            },
+        )
+    }
+
     fn end(self) -> Result<Self::Ok, Self::Error> {
         self.renderer.end()
     }
@@ -191,6 +209,21 @@ where
         self.renderer.inner_html(inner_html)
     }
 
+    #[inline]
+    fn hook(&mut self, hook: Hook) -> Result<(), Self::Error> {
+        self.renderer.hook(hook)
+    }
+
+    #[inline]
+    fn drawing(&mut self, drawing: Drawing) -> Result<(), Self::Error> {
+        self.renderer.drawing(drawing)
+    }
+
+    #[inline]
+    fn node_ref(&mut self, node_ref: NodeRef) -> Result<(), Self::Error> {
+        self.renderer.node_ref(node_ref)
+    }
+
     #[inline]
     fn end(self) -> Result<Self::Ok, Self::Error> {
         self.renderer.end()